@@ -0,0 +1,356 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+
+use crate::common::{Error, SearchFor};
+use crate::table::Article;
+
+#[cfg(debug_assertions)]
+mod mock;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+const PROGRESS_STREAM_CONTENT_TYPE: &str = "application/x-ndjson";
+
+pub const MAX_RETRIES: u32 = 3;
+const BASE_RETRY_DELAY_MS: u32 = 1000;
+const MAX_RETRY_DELAY_MS: u32 = 30_000;
+
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct SnowballParameters {
+    pub output_max_size: usize,
+    pub depth: u8,
+    pub input_id_list: Vec<String>,
+    pub search_for: SearchFor
+}
+
+#[derive(Serialize, Debug)]
+pub struct SnowballRequest<'a> {
+    pub schema_version: u32,
+    #[serde(flatten)]
+    pub parameters: &'a SnowballParameters
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SeedResolution {
+    pub input: String,
+    pub resolved: bool,
+    pub title: Option<String>,
+    #[serde(default)]
+    pub lens_id: Option<String>,
+    pub reason: Option<String>
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeKind {
+    /// `source` cites `target`
+    Reference,
+    /// `source` is cited by `target`
+    Citation
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CitationEdge {
+    pub source: String,
+    pub target: String,
+    pub kind: EdgeKind
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SnowballResponse {
+    pub schema_version: u32,
+    pub articles: Vec<Article>,
+    #[serde(default)]
+    pub seeds: Vec<SeedResolution>,
+    #[serde(default)]
+    pub edges: Vec<CitationEdge>
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SnowballResults {
+    pub articles: Vec<Article>,
+    pub seeds: Vec<SeedResolution>,
+    #[serde(default)]
+    pub edges: Vec<CitationEdge>
+}
+
+/// Told apart by the first character rather than an untagged enum, so that decoding errors keep their position
+fn parse_response_body(body: &str) -> Result<SnowballResults, Error> {
+    match body.trim_start().starts_with('[') {
+        true => Ok(SnowballResults { articles: serde_json::from_str(body)?, ..Default::default() }),
+        false => serde_json::from_str::<SnowballResponse>(body)?.into_results()
+    }
+}
+
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SnowballStage {
+    ResolvingSeeds,
+    FetchingReferences,
+    FetchingCitations,
+    Scoring
+}
+
+impl fmt::Display for SnowballStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnowballStage::ResolvingSeeds => write!(f, "Resolving seeds"),
+            SnowballStage::FetchingReferences => write!(f, "Fetching references"),
+            SnowballStage::FetchingCitations => write!(f, "Fetching citations"),
+            SnowballStage::Scoring => write!(f, "Scoring")
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct SnowballProgress {
+    pub stage: SnowballStage,
+    pub level: Option<u8>,
+    pub depth: Option<u8>,
+    pub done: Option<usize>,
+    pub total: Option<usize>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct RetryAttempt {
+    pub retry: u32,
+    pub delay_ms: u32,
+    pub reason: String
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum SnowballUpdate {
+    Progress(SnowballProgress),
+    Retrying(RetryAttempt)
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SnowballEvent {
+    Progress(SnowballProgress),
+    Result(SnowballResponse),
+    Error {
+        status: Option<u16>,
+        #[serde(flatten)]
+        body: BackendErrorBody
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BackendErrorBody {
+    pub code: Option<String>,
+    pub message: String
+}
+
+struct FailedAttempt {
+    error: Error,
+    retry_after_ms: Option<u32>
+}
+
+impl<E: Into<Error>> From<E> for FailedAttempt {
+    fn from(error: E) -> Self {
+        FailedAttempt { error: error.into(), retry_after_ms: None }
+    }
+}
+
+/// Rate limits, gateway errors and network failures are worth retrying, the search being read-only
+fn is_transient(error: &Error) -> bool {
+    match error {
+        Error::RateLimited(_) => true,
+        Error::Backend { status, .. } => matches!(status, 500 | 502 | 503 | 504),
+        Error::Request(gloo_net::Error::JsError(_)) => true,
+        _ => false
+    }
+}
+
+/// `Retry-After` is either a number of seconds or an HTTP date
+fn parse_retry_after(value: &str) -> Option<u32> {
+    if let Ok(seconds) = value.trim().parse::<u32>() {
+        return Some(seconds.saturating_mul(1000));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value.trim()).ok()?;
+    let delay_ms = (date.timestamp_millis() - chrono::Utc::now().timestamp_millis()).max(0);
+    Some(delay_ms.min(u32::MAX as i64) as u32)
+}
+
+/// Exponential backoff with jitter, so that clients rate limited together do not retry together
+fn backoff_delay_ms(retry: u32) -> u32 {
+    use rand::Rng;
+    let delay = BASE_RETRY_DELAY_MS
+        .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+        .min(MAX_RETRY_DELAY_MS);
+    rand::thread_rng().gen_range(delay/2..=delay)
+}
+
+impl BackendErrorBody {
+    fn into_error(self, status: u16) -> Error {
+        match status {
+            400 | 422 => Error::BadRequest(self.message),
+            429 => Error::RateLimited(self.message),
+            _ => Error::Backend { status, code: self.code, message: self.message }
+        }
+    }
+}
+
+pub struct ApiClient {
+    base_url: url::Url
+}
+
+impl ApiClient {
+    pub fn new(base_url: url::Url) -> Self {
+        ApiClient { base_url }
+    }
+
+    pub async fn snowball(&self,
+            parameters: &SnowballParameters,
+            abort_signal: Option<&web_sys::AbortSignal>,
+            on_update: &dyn Fn(SnowballUpdate)) -> Result<SnowballResults, Error> {
+        let on_progress = |progress| on_update(SnowballUpdate::Progress(progress));
+        let mut retry = 0;
+        loop {
+            let failed_attempt = match self.snowball_attempt(parameters, abort_signal, &on_progress).await {
+                Ok(results) => return Ok(results),
+                Err(failed_attempt) => failed_attempt
+            };
+
+            let aborted = abort_signal.is_some_and(web_sys::AbortSignal::aborted);
+            if aborted || retry >= MAX_RETRIES || !is_transient(&failed_attempt.error) {
+                return Err(failed_attempt.error);
+            }
+
+            retry += 1;
+            let delay_ms = failed_attempt.retry_after_ms
+                .unwrap_or_else(|| backoff_delay_ms(retry))
+                .min(MAX_RETRY_DELAY_MS);
+            on_update(SnowballUpdate::Retrying(RetryAttempt { retry, delay_ms, reason: failed_attempt.error.to_string() }));
+            gloo_timers::future::TimeoutFuture::new(delay_ms).await;
+        }
+    }
+
+    async fn snowball_attempt(&self,
+            parameters: &SnowballParameters,
+            abort_signal: Option<&web_sys::AbortSignal>,
+            on_progress: &dyn Fn(SnowballProgress)) -> Result<SnowballResults, FailedAttempt> {
+        #[cfg(debug_assertions)]
+        if self.base_url.scheme() == mock::SCHEME {
+            return mock::snowball_attempt(self.base_url.path(), abort_signal, on_progress).await;
+        }
+
+        let request = SnowballRequest {
+            schema_version: SCHEMA_VERSION,
+            parameters
+        };
+
+        let response = gloo_net::http::Request::post(self.base_url.as_str())
+            .abort_signal(abort_signal)
+            .header("Accept", &format!("{PROGRESS_STREAM_CONTENT_TYPE}, application/json"))
+            .json(&request)?
+            .send()
+            .await?;
+
+        let is_progress_stream = response
+            .headers()
+            .get("Content-Type")
+            .is_some_and(|content_type| content_type.starts_with(PROGRESS_STREAM_CONTENT_TYPE));
+        if response.ok() && is_progress_stream {
+            return Ok(read_progress_stream(&response, on_progress).await?);
+        }
+
+        let status = response.status();
+        let retry_after_ms = response.headers().get("Retry-After").as_deref().and_then(parse_retry_after);
+        let body = response.text().await?;
+
+        if !response.ok() {
+            let error = match serde_json::from_str::<BackendErrorBody>(&body) {
+                Ok(error_body) => error_body.into_error(status),
+                Err(_) => BackendErrorBody { code: None, message: body }.into_error(status)
+            };
+            return Err(FailedAttempt { error, retry_after_ms });
+        }
+
+        Ok(parse_response_body(&body)?)
+    }
+}
+
+impl SnowballResponse {
+    fn into_results(self) -> Result<SnowballResults, Error> {
+        match self.schema_version > SCHEMA_VERSION {
+            true => Err(Error::UnsupportedSchemaVersion(self.schema_version)),
+            false => Ok(SnowballResults { articles: self.articles, seeds: self.seeds, edges: self.edges })
+        }
+    }
+}
+
+fn handle_progress_line(line: &[u8], on_progress: &dyn Fn(SnowballProgress)) -> Result<Option<SnowballResults>, Error> {
+    if line.iter().all(u8::is_ascii_whitespace) {
+        return Ok(None);
+    }
+
+    match serde_json::from_slice::<SnowballEvent>(line)? {
+        SnowballEvent::Progress(progress) => {
+            on_progress(progress);
+            Ok(None)
+        },
+        SnowballEvent::Result(response) => response.into_results().map(Some),
+        SnowballEvent::Error { status, body } => Err(body.into_error(status.unwrap_or(500)))
+    }
+}
+
+async fn read_progress_stream(response: &gloo_net::http::Response, on_progress: &dyn Fn(SnowballProgress)) -> Result<SnowballResults, Error> {
+    let reader = response
+        .body()
+        .ok_or(Error::IncompleteProgressStream)?
+        .get_reader()
+        .unchecked_into::<web_sys::ReadableStreamDefaultReader>();
+
+    let mut buffer = Vec::<u8>::new();
+    loop {
+        let chunk = wasm_bindgen_futures::JsFuture::from(reader.read()).await?;
+        let done = js_sys::Reflect::get(&chunk, &"done".into())?.as_bool().unwrap_or(true);
+        let value = js_sys::Reflect::get(&chunk, &"value".into())?;
+        if !value.is_undefined() {
+            buffer.extend(js_sys::Uint8Array::new(&value).to_vec());
+        }
+
+        while let Some(newline) = buffer.iter().position(|byte| *byte == b'\n') {
+            let line = buffer.drain(..=newline).collect::<Vec<_>>();
+            if let Some(results) = handle_progress_line(&line, on_progress)? {
+                return Ok(results);
+            }
+        }
+
+        if done {
+            break;
+        }
+    }
+
+    handle_progress_line(&buffer, on_progress)?.ok_or(Error::IncompleteProgressStream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn response_body_without_schema_version() {
+        let results = parse_response_body(r#"[{"title": "A", "score": 3}]"#).unwrap();
+        assert_eq!(results.articles.len(), 1);
+        assert!(results.seeds.is_empty());
+    }
+
+    #[test]
+    fn response_body_with_schema_version() {
+        let results = parse_response_body(r#"{"schema_version": 1, "articles": [{"title": "A"}], "seeds": []}"#).unwrap();
+        assert_eq!(results.articles[0].title.as_deref(), Some("A"));
+    }
+
+    #[test]
+    fn response_body_errors_keep_their_position() {
+        let error = parse_response_body("{\"schema_version\": 1,\n\"articles\": [{\"score\": \"high\"}]}").unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("line 2"), "{message}");
+        assert!(message.contains("invalid type"), "{message}");
+    }
+}
@@ -0,0 +1,91 @@
+//! Fixture backend selected with an endpoint of the form `mock:<scenario>`, e.g. `?api_url=mock:slow` in debug builds.
+
+use std::cell::Cell;
+
+use gloo_timers::future::TimeoutFuture;
+
+use super::{BackendErrorBody, FailedAttempt, SnowballProgress, SnowballResponse, SnowballResults, SnowballStage};
+use crate::common::Error;
+
+pub const SCHEME: &str = "mock";
+
+const OK: &str = include_str!("../../fixtures/snowball_ok.json");
+const UNRESOLVED: &str = include_str!("../../fixtures/snowball_unresolved.json");
+const BAD_REQUEST: &str = include_str!("../../fixtures/error_bad_request.json");
+const SERVER_ERROR: &str = include_str!("../../fixtures/error_server.json");
+
+const SLOW_DELAY_MS: u32 = 120_000;
+const POLL_INTERVAL_MS: u32 = 100;
+
+thread_local! {
+    static RATE_LIMITED_ATTEMPTS: Cell<u32> = const { Cell::new(0) };
+}
+
+async fn sleep(delay_ms: u32, abort_signal: Option<&web_sys::AbortSignal>) -> Result<(), Error> {
+    let mut waited = 0;
+    while waited < delay_ms {
+        if abort_signal.is_some_and(web_sys::AbortSignal::aborted) {
+            return Err(Error::Cancelled);
+        }
+        TimeoutFuture::new(POLL_INTERVAL_MS).await;
+        waited += POLL_INTERVAL_MS;
+    }
+    Ok(())
+}
+
+fn results(fixture: &str) -> Result<SnowballResults, FailedAttempt> {
+    Ok(serde_json::from_str::<SnowballResponse>(fixture)?.into_results()?)
+}
+
+fn backend_error(fixture: &str, status: u16, retry_after_ms: Option<u32>) -> Result<SnowballResults, FailedAttempt> {
+    let error = serde_json::from_str::<BackendErrorBody>(fixture)?.into_error(status);
+    Err(FailedAttempt { error, retry_after_ms })
+}
+
+async fn report_progress(on_progress: &dyn Fn(SnowballProgress), abort_signal: Option<&web_sys::AbortSignal>) -> Result<(), Error> {
+    let stages = [
+        (SnowballStage::ResolvingSeeds, None),
+        (SnowballStage::FetchingReferences, Some(1)),
+        (SnowballStage::FetchingReferences, Some(2)),
+        (SnowballStage::FetchingCitations, Some(1)),
+        (SnowballStage::FetchingCitations, Some(2)),
+        (SnowballStage::Scoring, None)
+    ];
+
+    for (stage, level) in stages {
+        for done in (0..=100).step_by(25) {
+            on_progress(SnowballProgress { stage: stage.clone(), level, depth: level.map(|_| 2), done: Some(done), total: Some(100) });
+            sleep(POLL_INTERVAL_MS, abort_signal).await?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn snowball_attempt(scenario: &str,
+        abort_signal: Option<&web_sys::AbortSignal>,
+        on_progress: &dyn Fn(SnowballProgress)) -> Result<SnowballResults, FailedAttempt> {
+    match scenario {
+        "ok" => results(OK),
+        "progress" => {
+            report_progress(on_progress, abort_signal).await?;
+            results(OK)
+        },
+        "unresolved" => results(UNRESOLVED),
+        "slow" => {
+            sleep(SLOW_DELAY_MS, abort_signal).await?;
+            results(OK)
+        },
+        "bad-request" => backend_error(BAD_REQUEST, 400, None),
+        "server-error" => backend_error(SERVER_ERROR, 500, None),
+        "rate-limited" => {
+            // Fails twice then succeeds, so that the retries can be observed
+            let attempt = RATE_LIMITED_ATTEMPTS.with(|attempts| attempts.replace(attempts.get() + 1));
+            match attempt % 3 {
+                2 => results(OK),
+                _ => backend_error(SERVER_ERROR, 429, Some(2000))
+            }
+        },
+        "invalid-json" => Ok(serde_json::from_str::<SnowballResults>("{")?),
+        _ => Err(Error::BadRequest(format!("Unknown mock scenario \"{scenario}\"")).into())
+    }
+}
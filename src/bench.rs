@@ -0,0 +1,113 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rand::{Rng, SeedableRng, seq::SliceRandom};
+use yew::prelude::*;
+
+use crate::common::get_value;
+use crate::table::{article, Article, Table, Filters, build_index, filter_articles};
+
+const WORDS: [&str; 16] = ["cancer", "imaging", "cohort", "randomized", "trial", "meta-analysis", "radiology", "outcome",
+    "therapy", "review", "patients", "network", "learning", "diagnosis", "survival", "bias"];
+
+fn sentence(rng: &mut impl Rng, length: usize) -> String {
+    (0..length)
+        .map(|_| *WORDS.choose(rng).unwrap_or(&""))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn synthetic_articles(count: usize) -> Vec<Article> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+    let mut articles = (0..count)
+        .map(|i| Article {
+            id: i,
+            first_author: Some(format!("Author {}", rng.gen_range(0..count.max(1)))),
+            year_published: Some(rng.gen_range(1950..2024)),
+            journal: Some(format!("Journal of {}", sentence(&mut rng, 2))),
+            title: Some(sentence(&mut rng, 12)),
+            summary: Some(sentence(&mut rng, 250)),
+            doi: Some(format!("10.0000/bench.{i}")),
+            citations: Some(rng.gen_range(0..5000)),
+            score: Some(rng.gen_range(0..1000)),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    articles.sort_by_key(|article| std::cmp::Reverse(article.score.unwrap_or_default()));
+    article::compute_metrics(&mut articles, 3);
+    articles
+}
+
+fn now() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map(|performance| performance.now())
+        .unwrap_or_else(js_sys::Date::now)
+}
+
+fn measure_filtering(articles: &[Article]) -> Vec<(String, f64, usize)> {
+    let start = now();
+    let index = build_index(articles);
+    let indexing = ("(index build)".to_string(), now() - start, index.len());
+
+    let filtering = ["", "c", "cancer", "2019", "radiology trial", "zzz"]
+        .iter()
+        .map(|pattern| {
+            let start = now();
+            let matches = filter_articles(&index, pattern, &Filters::default()).len();
+            (format!("{pattern:?}"), now() - start, matches)
+        });
+
+    std::iter::once(indexing).chain(filtering).collect()
+}
+
+#[function_component(Benchmark)]
+pub fn benchmark() -> Html {
+    let size_node = use_node_ref();
+    let articles = use_state(|| None::<Rc<RefCell<Vec<Article>>>>);
+    let timings = use_state(Vec::<(String, f64, usize)>::new);
+
+    let onclick = {
+        let size_node = size_node.clone();
+        let articles = articles.clone();
+        let timings = timings.clone();
+        Callback::from(move |_: MouseEvent| {
+            let size = get_value(&size_node)
+                .and_then(|value| value.parse::<usize>().ok())
+                .unwrap_or(1000);
+            let generated = synthetic_articles(size);
+            timings.set(measure_filtering(&generated));
+            articles.set(Some(Rc::new(RefCell::new(generated))));
+        })
+    };
+
+    html! {
+        <div>
+            <div class="container-md">
+                <h1 class="mb-4"><i class="bi bi-speedometer px-2"></i>{"Table benchmark"}</h1>
+                <div class="row align-items-end mb-3">
+                    <div class="col-auto">
+                        <label class="form-label" for="benchmarkSize">{"Number of synthetic articles"}</label>
+                        <input type="number" class="form-control" id="benchmarkSize" value="5000" ref={size_node}/>
+                    </div>
+                    <div class="col-auto">
+                        <button class="btn btn-outline-secondary" {onclick}>{"Generate and measure"}</button>
+                    </div>
+                </div>
+                if !timings.is_empty() {
+                    <table class="table table-sm">
+                        <thead><tr><th>{"Global filter"}</th><th>{"Matches"}</th><th>{"Time (ms)"}</th></tr></thead>
+                        <tbody>
+                            { timings.iter().map(|(pattern, time, matches)| html! {
+                                <tr><td>{pattern}</td><td>{matches}</td><td>{format!("{time:.2}")}</td></tr>
+                            }).collect::<Html>() }
+                        </tbody>
+                    </table>
+                }
+            </div>
+            if let Some(articles) = (*articles).clone() {
+                <Table {articles}/>
+            }
+        </div>
+    }
+}
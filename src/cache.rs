@@ -0,0 +1,285 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{JsCast, JsValue, closure::Closure};
+use web_sys::{IdbDatabase, IdbRequest, IdbTransactionMode};
+use yew::prelude::*;
+
+use crate::api::{SnowballParameters, SnowballResults};
+use crate::common::Error;
+
+const DATABASE_NAME: &str = "biblizap";
+const DATABASE_VERSION: u32 = 1;
+const STORE_NAME: &str = "responses";
+
+pub const CACHE_TTL_MS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Search parameters with the ids sorted, deduplicated and lowercased, so that equivalent searches share a key
+fn normalized(endpoint: &url::Url, parameters: &SnowballParameters) -> String {
+    let mut input_id_list = parameters.input_id_list
+        .iter()
+        .map(|id| id.trim().to_lowercase())
+        .filter(|id| !id.is_empty())
+        .collect::<Vec<_>>();
+    input_id_list.sort();
+    input_id_list.dedup();
+
+    format!("{}|{}|{}|{:?}|{}", endpoint, parameters.output_max_size, parameters.depth, parameters.search_for, input_id_list.join(" "))
+}
+
+/// 64-bit FNV-1a, stable across builds unlike the standard library hasher
+fn fnv1a(value: &str) -> u64 {
+    value.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+pub fn cache_key(endpoint: &url::Url, parameters: &SnowballParameters) -> String {
+    format!("{:016x}", fnv1a(&normalized(endpoint, parameters)))
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CacheEntry {
+    pub key: String,
+    pub created_at_ms: f64,
+    pub parameters: SnowballParameters,
+    pub results: SnowballResults
+}
+
+impl CacheEntry {
+    pub fn is_expired(&self) -> bool {
+        js_sys::Date::now() - self.created_at_ms > CACHE_TTL_MS
+    }
+}
+
+fn cache_error(value: JsValue) -> Error {
+    let message = value
+        .dyn_ref::<js_sys::Error>()
+        .map(|error| String::from(error.message()))
+        .or(value.as_string())
+        .unwrap_or_else(|| "IndexedDB request failed".to_string());
+    Error::Cache(message)
+}
+
+async fn completion(request: &IdbRequest) -> Result<JsValue, Error> {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        let onsuccess = Closure::once_into_js(move || { resolve.call0(&JsValue::NULL).unwrap_or_default(); });
+        let onerror = Closure::once_into_js(move || { reject.call0(&JsValue::NULL).unwrap_or_default(); });
+        request.set_onsuccess(Some(onsuccess.unchecked_ref()));
+        request.set_onerror(Some(onerror.unchecked_ref()));
+    });
+
+    if wasm_bindgen_futures::JsFuture::from(promise).await.is_err() {
+        let error = request.error().ok().flatten().map(|error| error.message());
+        return Err(Error::Cache(error.unwrap_or_else(|| "IndexedDB request failed".to_string())));
+    }
+
+    request.result().map_err(cache_error)
+}
+
+async fn open_database() -> Result<IdbDatabase, Error> {
+    let factory = gloo_utils::window()
+        .indexed_db()
+        .map_err(cache_error)?
+        .ok_or(Error::Cache("IndexedDB is not available".to_string()))?;
+    let request = factory.open_with_u32(DATABASE_NAME, DATABASE_VERSION).map_err(cache_error)?;
+
+    let onupgradeneeded = {
+        let request = request.clone();
+        Closure::once_into_js(move || {
+            if let Ok(database) = request.result().map(JsValue::unchecked_into::<IdbDatabase>) {
+                if !database.object_store_names().contains(STORE_NAME) {
+                    if let Err(error) = database.create_object_store(STORE_NAME) {
+                        gloo_console::log!(error);
+                    }
+                }
+            }
+        })
+    };
+    request.set_onupgradeneeded(Some(onupgradeneeded.unchecked_ref()));
+
+    Ok(completion(&request).await?.unchecked_into())
+}
+
+async fn store_request(mode: IdbTransactionMode, make_request: impl FnOnce(&web_sys::IdbObjectStore) -> Result<IdbRequest, JsValue>) -> Result<JsValue, Error> {
+    let database = open_database().await?;
+    let store = database
+        .transaction_with_str_and_mode(STORE_NAME, mode)
+        .and_then(|transaction| transaction.object_store(STORE_NAME))
+        .map_err(cache_error)?;
+    let request = make_request(&store).map_err(cache_error)?;
+    let result = completion(&request).await;
+    database.close();
+    result
+}
+
+pub async fn get(key: &str) -> Result<Option<CacheEntry>, Error> {
+    let value = store_request(IdbTransactionMode::Readonly, |store| store.get(&key.into())).await?;
+    let Some(json) = value.as_string() else {
+        return Ok(None);
+    };
+
+    let entry = serde_json::from_str::<CacheEntry>(&json)?;
+    if entry.is_expired() {
+        delete(key).await?;
+        return Ok(None);
+    }
+    Ok(Some(entry))
+}
+
+pub async fn put(entry: &CacheEntry) -> Result<(), Error> {
+    let json = serde_json::to_string(entry)?;
+    store_request(IdbTransactionMode::Readwrite, |store| store.put_with_key(&json.into(), &entry.key.as_str().into())).await?;
+    Ok(())
+}
+
+pub async fn delete(key: &str) -> Result<(), Error> {
+    store_request(IdbTransactionMode::Readwrite, |store| store.delete(&key.into())).await?;
+    Ok(())
+}
+
+pub async fn clear() -> Result<(), Error> {
+    store_request(IdbTransactionMode::Readwrite, |store| store.clear()).await?;
+    Ok(())
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct CacheEntrySummary {
+    pub key: String,
+    pub created_at_ms: f64,
+    pub parameters: SnowballParameters,
+    pub article_count: usize,
+    pub size_bytes: usize,
+    pub expired: bool
+}
+
+pub async fn summaries() -> Result<Vec<CacheEntrySummary>, Error> {
+    let values = store_request(IdbTransactionMode::Readonly, |store| store.get_all()).await?;
+    let values = values.dyn_into::<js_sys::Array>().map_err(cache_error)?;
+
+    let mut summaries = values
+        .iter()
+        .filter_map(|value| value.as_string())
+        .map(|json| {
+            let entry = serde_json::from_str::<CacheEntry>(&json)?;
+            Ok(CacheEntrySummary {
+                expired: entry.is_expired(),
+                key: entry.key,
+                created_at_ms: entry.created_at_ms,
+                parameters: entry.parameters,
+                article_count: entry.results.articles.len(),
+                size_bytes: json.len()
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    summaries.sort_by(|a, b| b.created_at_ms.total_cmp(&a.created_at_ms));
+
+    Ok(summaries)
+}
+
+fn format_size(size_bytes: usize) -> String {
+    match size_bytes {
+        0..=1023 => format!("{size_bytes} B"),
+        1024..=1_048_575 => format!("{:.1} kB", size_bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", size_bytes as f64 / 1_048_576.0)
+    }
+}
+
+#[function_component(CacheManagement)]
+pub fn cache_management() -> Html {
+    let summaries = use_state(|| None::<Result<Vec<CacheEntrySummary>, String>>);
+    let reload = {
+        let summaries = summaries.clone();
+        Callback::from(move |_: ()| {
+            let summaries = summaries.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                summaries.set(Some(self::summaries().await.map_err(|error| error.to_string())));
+            });
+        })
+    };
+    {
+        let reload = reload.clone();
+        use_effect_with((), move |_| reload.emit(()));
+    }
+
+    let on_clear = {
+        let reload = reload.clone();
+        Callback::from(move |_: MouseEvent| {
+            let reload = reload.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(error) = clear().await {
+                    gloo_console::log!(format!("{error}"));
+                }
+                reload.emit(());
+            });
+        })
+    };
+
+    let content = match summaries.as_ref() {
+        None => html! { <div class="spinner-border" role="status"><span class="visually-hidden">{"Loading..."}</span></div> },
+        Some(Err(error)) => html! { <div class="alert alert-danger" role="alert">{error}</div> },
+        Some(Ok(summaries)) => {
+            let total_size = summaries.iter().map(|summary| summary.size_bytes).sum::<usize>();
+            html! {
+                <>
+                    <p>{format!("{} cached searches, {} in total. Cached results expire after {} hours.", summaries.len(), format_size(total_size), CACHE_TTL_MS / 3_600_000.0)}</p>
+                    <button class="btn btn-outline-danger mb-3" onclick={on_clear} disabled={summaries.is_empty()}><i class="bi bi-trash me-2"></i>{"Clear the cache"}</button>
+                    if !summaries.is_empty() {
+                        <table class="table table-sm">
+                            <thead>
+                                <tr><th>{"Seeds"}</th><th>{"Depth"}</th><th>{"Direction"}</th><th>{"Articles"}</th><th>{"Size"}</th><th>{"Cached"}</th><th></th></tr>
+                            </thead>
+                            <tbody>
+                                { summaries.iter().map(|summary| html! { <CacheEntryRow summary={summary.clone()} reload={reload.clone()}/> }).collect::<Html>() }
+                            </tbody>
+                        </table>
+                    }
+                </>
+            }
+        }
+    };
+
+    html! {
+        <div class="container-md">
+            <h1 class="mb-4"><i class="bi bi-database px-2"></i>{"Cached searches"}</h1>
+            {content}
+        </div>
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+struct CacheEntryRowProps {
+    summary: CacheEntrySummary,
+    reload: Callback<()>
+}
+
+#[function_component(CacheEntryRow)]
+fn cache_entry_row(props: &CacheEntryRowProps) -> Html {
+    let on_delete = {
+        let key = props.summary.key.clone();
+        let reload = props.reload.clone();
+        Callback::from(move |_: MouseEvent| {
+            let key = key.clone();
+            let reload = reload.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(error) = delete(&key).await {
+                    gloo_console::log!(format!("{error}"));
+                }
+                reload.emit(());
+            });
+        })
+    };
+
+    let summary = &props.summary;
+    let cached_at = chrono::DateTime::from_timestamp_millis(summary.created_at_ms as i64)
+        .map(|date| date.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+
+    html! {
+        <tr class={classes!(summary.expired.then_some("text-body-secondary"))}>
+            <td style="word-wrap: break-word">{summary.parameters.input_id_list.join(" ")}</td>
+            <td>{summary.parameters.depth}</td>
+            <td>{format!("{:?}", summary.parameters.search_for)}</td>
+            <td>{summary.article_count}</td>
+            <td>{format_size(summary.size_bytes)}</td>
+            <td>{cached_at}{if summary.expired { " (expired)" } else { "" }}</td>
+            <td><button class="btn btn-outline-secondary btn-sm" title="Delete" onclick={on_delete}><i class="bi bi-trash"></i></button></td>
+        </tr>
+    }
+}
@@ -1,5 +1,5 @@
 use thiserror::Error;
-use wasm_bindgen::JsValue;
+use wasm_bindgen::{JsCast, JsValue};
 use web_sys::Navigator;
 use yew::prelude::*;
 
@@ -30,7 +30,27 @@ pub enum Error {
     #[error(transparent)]
     UrlParse(#[from] url::ParseError),
     #[error("Unrecognized User Agent : {0}")]
-    UnrecognizedUserAgent(String)
+    UnrecognizedUserAgent(String),
+    #[error("Invalid request : {0}")]
+    BadRequest(String),
+    #[error("Rate limited : {0}")]
+    RateLimited(String),
+    #[error("Backend error {status} : {message}")]
+    Backend { status: u16, code: Option<String>, message: String },
+    #[error("Unsupported API schema version : {0}")]
+    UnsupportedSchemaVersion(u32),
+    #[error("Search cancelled")]
+    Cancelled,
+    #[error("Search timed out after {0} seconds")]
+    TimedOut(u32),
+    #[error("The progress stream ended before the results")]
+    IncompleteProgressStream,
+    #[error("Cache error : {0}")]
+    Cache(String),
+    #[error("Import error : {0}")]
+    Import(String),
+    #[error("Unsupported session version {0}")]
+    UnsupportedSessionVersion(u32)
 }
 
 #[derive(Error, Debug)]
@@ -45,6 +65,82 @@ pub enum NodeRefMissingValue {
     SearchFor
 }
 
+#[derive(Clone, PartialEq, Debug)]
+pub enum RecoveryAction {
+    FixIds,
+    Retry,
+    ReduceDepth,
+    Reload,
+    ChooseFile,
+    SwitchBrowser,
+    ReportBug
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ErrorReport {
+    pub explanation: &'static str,
+    pub action: RecoveryAction,
+    pub details: String
+}
+
+impl Error {
+    pub fn report(&self) -> ErrorReport {
+        let (explanation, action) = match self {
+            Error::Json(_) =>
+                ("The server answered with data that BibliZap could not read.", RecoveryAction::ReportBug),
+            Error::UnrecognizedUserAgent(_) =>
+                ("The BibliZap browser extension is only available for Firefox and Chrome.", RecoveryAction::SwitchBrowser),
+            Error::Io(_) | Error::Csv(_) | Error::Xlsx(_) | Error::CsvIntoInner(_) =>
+                ("The file could not be generated.", RecoveryAction::ReportBug),
+            Error::Request(_) =>
+                ("The BibliZap server could not be reached. Please check your internet connection.", RecoveryAction::Retry),
+            Error::JsValueString(_) | Error::HtmlElementDynRef =>
+                ("Your browser reported an unexpected error.", RecoveryAction::Reload),
+            Error::TryFromInt(_) | Error::ParseInt(_) | Error::NodeRefMissingValue(_) =>
+                ("Some of the search options could not be read.", RecoveryAction::Reload),
+            Error::UrlParse(_) =>
+                ("The address of the BibliZap server is invalid.", RecoveryAction::ReportBug),
+            Error::BadRequest(_) =>
+                ("The server rejected the search. Some of the identifiers may be malformed.", RecoveryAction::FixIds),
+            Error::RateLimited(_) =>
+                ("The Lens is receiving too many requests at the moment.", RecoveryAction::Retry),
+            Error::Backend { status, .. } if *status >= 500 =>
+                ("The BibliZap server failed to complete the search. Very large searches may exceed its limits.", RecoveryAction::ReduceDepth),
+            Error::Backend { .. } =>
+                ("The BibliZap server could not handle the search.", RecoveryAction::Retry),
+            Error::UnsupportedSchemaVersion(_) =>
+                ("This page is older than the BibliZap server.", RecoveryAction::Reload),
+            Error::Cancelled =>
+                ("The search was cancelled.", RecoveryAction::Retry),
+            Error::TimedOut(_) =>
+                ("The search took too long to complete.", RecoveryAction::ReduceDepth),
+            Error::IncompleteProgressStream =>
+                ("The connection to the BibliZap server was interrupted.", RecoveryAction::Retry),
+            Error::Cache(_) =>
+                ("The results stored in your browser could not be accessed.", RecoveryAction::Reload),
+            Error::UnsupportedSessionVersion(_) =>
+                ("This session was saved by a newer version of BibliZap.", RecoveryAction::Reload),
+            Error::Import(_) =>
+                ("The file could not be read. Only the Excel, CSV and JSON files downloaded from BibliZap can be opened.", RecoveryAction::ChooseFile),
+        };
+
+        ErrorReport {
+            explanation,
+            action,
+            details: format!("{self}\n{self:?}")
+        }
+    }
+}
+
+/// Copies `text` through the asynchronous Clipboard API, which `web_sys` only exposes as unstable
+pub fn copy_to_clipboard(text: &str) -> Result<js_sys::Promise, Error> {
+    let navigator = gloo_utils::window().navigator();
+    let clipboard = js_sys::Reflect::get(&navigator, &"clipboard".into())?;
+    let write_text = js_sys::Reflect::get(&clipboard, &"writeText".into())?.dyn_into::<js_sys::Function>()?;
+
+    Ok(write_text.call1(&clipboard, &text.into())?.dyn_into::<js_sys::Promise>()?)
+}
+
 impl From<JsValue> for Error {
     fn from(value: JsValue) -> Self {
         Error::JsValueString(value.as_string().unwrap_or_default())
@@ -56,10 +152,13 @@ pub enum CurrentPage {
     BibliZapApp,
     HowItWorks,
     Contact,
-    LegalInformation
+    LegalInformation,
+    Cache,
+    Compare,
+    Benchmark
 }
 
-#[derive(Clone, PartialEq, Default, Debug, serde::Serialize)]
+#[derive(Clone, PartialEq, Default, Debug, serde::Serialize, serde::Deserialize)]
 pub enum SearchFor {
     References,
     Citations,
@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::cache::{self, CacheEntrySummary};
+use crate::common::Error;
+use crate::table::Article;
+use crate::table::download::download_bytes_as_file;
+use crate::table::import::{parse_export, read_file};
+
+/// Keys under which an article is matched between two searches, the title being used when it has no identifier
+fn match_keys(article: &Article) -> Vec<String> {
+    let identifiers = article.identifiers();
+    match identifiers.is_empty() {
+        true => article.title.iter().map(|title| format!("title:{}", title.trim().to_lowercase())).collect(),
+        false => identifiers
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SearchDiff {
+    pub only_a: Vec<Article>,
+    pub only_b: Vec<Article>,
+    pub both: Vec<(Article, Article)>
+}
+
+pub fn diff(a: &[Article], b: &[Article]) -> SearchDiff {
+    let mut b_index = HashMap::<String, usize>::new();
+    for (index, article) in b.iter().enumerate() {
+        for key in match_keys(article) {
+            b_index.entry(key).or_insert(index);
+        }
+    }
+
+    let mut matched = vec![false; b.len()];
+    let mut result = SearchDiff::default();
+    for article in a {
+        let counterpart = match_keys(article)
+            .iter()
+            .filter_map(|key| b_index.get(key).copied())
+            .find(|index| !matched[*index]);
+        match counterpart {
+            Some(index) => {
+                matched[index] = true;
+                result.both.push((article.clone(), b[index].clone()));
+            },
+            None => result.only_a.push(article.clone())
+        }
+    }
+    result.only_b = b.iter().zip(matched).filter(|(_, matched)| !matched).map(|(article, _)| article.clone()).collect();
+
+    let change = |(a, b): &(Article, Article)| (b.score.unwrap_or_default() - a.score.unwrap_or_default()).abs();
+    result.both.sort_by_key(|pair| std::cmp::Reverse(change(pair)));
+    result
+}
+
+pub fn diff_to_excel(diff: &SearchDiff) -> Result<Vec<u8>, Error> {
+    use rust_xlsxwriter::Workbook;
+
+    let mut workbook = Workbook::new();
+    let text = |value: &Option<String>| value.clone().unwrap_or_default();
+    let number = |value: Option<i32>| value.map(|x| x.to_string()).unwrap_or_default();
+
+    for (name, articles) in [("Only in A", &diff.only_a), ("Only in B", &diff.only_b)] {
+        let worksheet = workbook.add_worksheet().set_name(name)?;
+        for (col, header) in ["Title", "First author", "Year published", "doi", "Score"].into_iter().enumerate() {
+            worksheet.write_string(0, col.try_into()?, header)?;
+        }
+        for (row, article) in articles.iter().enumerate() {
+            let row: u32 = (row + 1).try_into()?;
+            let values = [text(&article.title), text(&article.first_author), number(article.year_published), text(&article.doi), number(article.score)];
+            for (col, value) in values.into_iter().enumerate() {
+                worksheet.write_string(row, col.try_into()?, value)?;
+            }
+        }
+        worksheet.autofit();
+    }
+
+    let worksheet = workbook.add_worksheet().set_name("In both")?;
+    for (col, header) in ["Title", "First author", "Year published", "doi", "Score A", "Score B", "Change"].into_iter().enumerate() {
+        worksheet.write_string(0, col.try_into()?, header)?;
+    }
+    for (row, (a, b)) in diff.both.iter().enumerate() {
+        let row: u32 = (row + 1).try_into()?;
+        let change = b.score.zip(a.score).map(|(b, a)| b - a);
+        let values = [text(&a.title), text(&a.first_author), number(a.year_published), text(&a.doi), number(a.score), number(b.score), number(change)];
+        for (col, value) in values.into_iter().enumerate() {
+            worksheet.write_string(row, col.try_into()?, value)?;
+        }
+    }
+    worksheet.autofit();
+
+    Ok(workbook.save_to_buffer()?)
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Candidate {
+    Cached(CacheEntrySummary),
+    Imported(String, Rc<Vec<Article>>)
+}
+
+impl Candidate {
+    fn label(&self) -> String {
+        match self {
+            Candidate::Cached(summary) => format!("{} (depth {}, {:?}, {} articles)", summary.parameters.input_id_list.join(" "),
+                summary.parameters.depth, summary.parameters.search_for, summary.article_count),
+            Candidate::Imported(filename, articles) => format!("{filename} ({} articles)", articles.len())
+        }
+    }
+
+    async fn articles(&self) -> Result<Vec<Article>, Error> {
+        match self {
+            Candidate::Cached(summary) => match cache::get(&summary.key).await? {
+                Some(entry) => Ok(entry.results.articles),
+                None => Err(Error::Cache("This search expired from the cache".to_string()))
+            },
+            Candidate::Imported(_, articles) => Ok(articles.deref().clone())
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+struct CandidateSelectProps {
+    id: AttrValue,
+    label: AttrValue,
+    candidates: Vec<Candidate>,
+    selected: UseStateHandle<Option<usize>>
+}
+
+#[function_component(CandidateSelect)]
+fn candidate_select(props: &CandidateSelectProps) -> Html {
+    let onchange = {
+        let selected = props.selected.clone();
+        Callback::from(move |event: Event| {
+            let value = event.target_unchecked_into::<web_sys::HtmlSelectElement>().value();
+            selected.set(value.parse().ok());
+        })
+    };
+
+    html! {
+        <div class="col-md-6 mb-3">
+            <label class="form-label" for={props.id.clone()}>{props.label.clone()}</label>
+            <select class="form-select" id={props.id.clone()} {onchange}>
+                <option value="" selected={props.selected.is_none()}>{"Choose a search"}</option>
+                { props.candidates.iter().enumerate().map(|(index, candidate)| html! {
+                    <option value={index.to_string()} selected={*props.selected == Some(index)}>{candidate.label()}</option>
+                }).collect::<Html>() }
+            </select>
+        </div>
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+struct DiffSectionProps {
+    title: AttrValue,
+    articles: Vec<Article>
+}
+
+#[function_component(DiffSection)]
+fn diff_section(props: &DiffSectionProps) -> Html {
+    html! {
+        <details class="mb-3">
+            <summary><strong>{format!("{} ({})", props.title, props.articles.len())}</strong></summary>
+            <table class="table table-sm">
+                <thead><tr><th>{"Title"}</th><th>{"First author"}</th><th>{"Year"}</th><th>{"Score"}</th></tr></thead>
+                <tbody>
+                    { props.articles.iter().map(|article| html! {
+                        <tr>
+                            <td><a href={article.doi_link()}>{article.title.clone().unwrap_or_default()}</a></td>
+                            <td>{article.first_author.clone().unwrap_or_default()}</td>
+                            <td>{article.year_published.map(|x| x.to_string()).unwrap_or_default()}</td>
+                            <td>{article.score.unwrap_or_default()}</td>
+                        </tr>
+                    }).collect::<Html>() }
+                </tbody>
+            </table>
+        </details>
+    }
+}
+
+#[function_component(CompareSearches)]
+pub fn compare_searches() -> Html {
+    let candidates = use_state(Vec::<Candidate>::new);
+    let load_error = use_state(|| None::<String>);
+    {
+        let candidates = candidates.clone();
+        let load_error = load_error.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                match cache::summaries().await {
+                    Ok(summaries) => candidates.set(summaries.into_iter().filter(|summary| !summary.expired).map(Candidate::Cached).collect()),
+                    Err(error) => load_error.set(Some(error.to_string()))
+                }
+            });
+        });
+    }
+
+    let onimport = {
+        let candidates = candidates.clone();
+        let load_error = load_error.clone();
+        Callback::from(move |event: Event| {
+            let input = event.target_unchecked_into::<web_sys::HtmlInputElement>();
+            let candidates = candidates.clone();
+            let load_error = load_error.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let Some((filename, bytes)) = read_file(&input).await else { return };
+                match bytes.and_then(|bytes| parse_export(&filename, &bytes)) {
+                    Ok(articles) => {
+                        let mut updated = candidates.deref().clone();
+                        updated.push(Candidate::Imported(filename, Rc::new(articles)));
+                        candidates.set(updated);
+                    },
+                    Err(error) => load_error.set(Some(error.to_string()))
+                }
+                input.set_value("");
+            });
+        })
+    };
+
+    let selected_a = use_state(|| None::<usize>);
+    let selected_b = use_state(|| None::<usize>);
+    let result = use_state(|| None::<Result<SearchDiff, String>>);
+
+    let oncompare = {
+        let candidates = candidates.clone();
+        let selected_a = selected_a.clone();
+        let selected_b = selected_b.clone();
+        let result = result.clone();
+        Callback::from(move |_: MouseEvent| {
+            let (Some(a), Some(b)) = (*selected_a, *selected_b) else { return };
+            let (a, b) = (candidates[a].clone(), candidates[b].clone());
+            let result = result.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let compared = async {
+                    Ok::<_, Error>(diff(&a.articles().await?, &b.articles().await?))
+                }.await;
+                result.set(Some(compared.map_err(|error| error.to_string())));
+            });
+        })
+    };
+
+    let ondownload = {
+        let result = result.clone();
+        Callback::from(move |_: MouseEvent| {
+            let Some(Ok(diff)) = result.deref() else { return };
+            let timestamp = chrono::Local::now().to_rfc3339();
+            let downloaded = diff_to_excel(diff).and_then(|bytes| download_bytes_as_file(&bytes, &format!("BibliZap-comparison-{timestamp}.xlsx")));
+            if let Err(error) = downloaded {
+                gloo_console::log!(format!("{error}"));
+            }
+        })
+    };
+
+    let content = match result.as_ref() {
+        None => html! {},
+        Some(Err(error)) => html! { <div class="alert alert-danger" role="alert">{error}</div> },
+        Some(Ok(diff)) => html! {
+            <>
+                <p>{format!("{} articles only in A, {} only in B, {} in both.", diff.only_a.len(), diff.only_b.len(), diff.both.len())}</p>
+                <button class="btn btn-outline-secondary mb-3" onclick={ondownload}><i class="bi bi-download me-2"></i>{"Download as a spreadsheet"}</button>
+                <DiffSection title="Only in A" articles={diff.only_a.clone()}/>
+                <DiffSection title="Only in B" articles={diff.only_b.clone()}/>
+                <details class="mb-3" open=true>
+                    <summary><strong>{format!("In both ({})", diff.both.len())}</strong></summary>
+                    <table class="table table-sm">
+                        <thead><tr><th>{"Title"}</th><th>{"Score A"}</th><th>{"Score B"}</th><th>{"Change"}</th></tr></thead>
+                        <tbody>
+                            { diff.both.iter().map(|(a, b)| {
+                                let change = b.score.unwrap_or_default() - a.score.unwrap_or_default();
+                                html! {
+                                    <tr>
+                                        <td><a href={a.doi_link()}>{a.title.clone().unwrap_or_default()}</a></td>
+                                        <td>{a.score.unwrap_or_default()}</td>
+                                        <td>{b.score.unwrap_or_default()}</td>
+                                        <td class={classes!(if change > 0 { "text-success" } else if change < 0 { "text-danger" } else { "" })}>{format!("{change:+}")}</td>
+                                    </tr>
+                                }
+                            }).collect::<Html>() }
+                        </tbody>
+                    </table>
+                </details>
+            </>
+        }
+    };
+
+    html! {
+        <div class="container-md">
+            <h1 class="mb-4"><i class="bi bi-arrow-left-right px-2"></i>{"Compare two searches"}</h1>
+            if let Some(error) = load_error.as_ref() {
+                <div class="alert alert-danger" role="alert">{error}</div>
+            }
+            <div class="mb-3">
+                <label class="form-label" for="compareImportInput">{"Searches cached in this browser can be compared, as well as files downloaded from BibliZap"}</label>
+                <input class="form-control" type="file" id="compareImportInput" accept=".xlsx,.csv,.json" onchange={onimport}/>
+            </div>
+            <div class="row">
+                <CandidateSelect id="compareA" label="Search A" candidates={candidates.deref().clone()} selected={selected_a.clone()}/>
+                <CandidateSelect id="compareB" label="Search B" candidates={candidates.deref().clone()} selected={selected_b.clone()}/>
+            </div>
+            <button class="btn btn-outline-primary mb-4" onclick={oncompare} disabled={selected_a.is_none() || selected_b.is_none()}>{"Compare"}</button>
+            {content}
+        </div>
+    }
+}
@@ -0,0 +1,120 @@
+//! Backend endpoint, taken in this order from the `api_url` query parameter (debug builds only), from the
+//! `config.json` file served next to `index.html`, from `BIBLIZAP_API_URL` at build time, and otherwise `/api`
+//! on the same origin.
+//!
+//! `config.json` lets a deployment point to its backend without rebuilding, see `config.example.json`:
+//!
+//! ```json
+//! { "api_url": "https://biblizap.example.org/api" }
+//! ```
+//!
+//! `api_url` may be relative to the page. A missing file or `api_url` falls back to the build time value,
+//! and the search form stays disabled until the file has been fetched or has failed to load.
+
+use std::fmt;
+
+use serde::Deserialize;
+use yew::prelude::*;
+
+use crate::common::Error;
+
+/// Backend base URL baked in at build time, e.g. `BIBLIZAP_API_URL=https://staging.example.org/api trunk build`
+const BUILD_TIME_API_URL: Option<&str> = option_env!("BIBLIZAP_API_URL");
+
+const API_URL_QUERY_PARAMETER: &str = "api_url";
+
+#[derive(Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct Config {
+    pub api_url: Option<String>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum EndpointSource {
+    QueryParameter,
+    ConfigFile,
+    BuildTime,
+    SameOrigin
+}
+
+impl fmt::Display for EndpointSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EndpointSource::QueryParameter => write!(f, "query parameter"),
+            EndpointSource::ConfigFile => write!(f, "config.json"),
+            EndpointSource::BuildTime => write!(f, "build time"),
+            EndpointSource::SameOrigin => write!(f, "same origin")
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Endpoint {
+    pub url: url::Url,
+    pub source: EndpointSource
+}
+
+fn document_url() -> Result<url::Url, Error> {
+    let mut url = url::Url::parse(&gloo_utils::document().document_uri()?)?;
+    url.set_fragment(None);
+    url.set_query(None);
+    Ok(url)
+}
+
+impl Endpoint {
+    pub fn initial() -> Result<Self, Error> {
+        let document_uri = url::Url::parse(&gloo_utils::document().document_uri()?)?;
+        let document_url = document_url()?;
+
+        if cfg!(debug_assertions) {
+            let from_query = document_uri
+                .query_pairs()
+                .find(|(k, _)| k.eq(API_URL_QUERY_PARAMETER))
+                .map(|(_, v)| v.into_owned());
+            if let Some(api_url) = from_query {
+                return Ok(Endpoint { url: document_url.join(&api_url)?, source: EndpointSource::QueryParameter });
+            }
+        }
+
+        if let Some(api_url) = BUILD_TIME_API_URL {
+            return Ok(Endpoint { url: document_url.join(api_url)?, source: EndpointSource::BuildTime });
+        }
+
+        Ok(Endpoint { url: document_url.join("/api")?, source: EndpointSource::SameOrigin })
+    }
+
+    pub async fn from_config_file() -> Result<Option<Self>, Error> {
+        let document_url = document_url()?;
+        let response = gloo_net::http::Request::get(document_url.join("config.json")?.as_str())
+            .send()
+            .await?;
+
+        if !response.ok() {
+            return Ok(None);
+        }
+
+        let config = response.json::<Config>().await?;
+        match config.api_url {
+            Some(api_url) => Ok(Some(Endpoint { url: document_url.join(&api_url)?, source: EndpointSource::ConfigFile })),
+            None => Ok(None)
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct DebugFooterProps {
+    pub endpoint: Option<Endpoint>
+}
+
+#[function_component(DebugFooter)]
+pub fn debug_footer(props: &DebugFooterProps) -> Html {
+    let endpoint = match &props.endpoint {
+        Some(endpoint) => format!("{} ({})", endpoint.url, endpoint.source),
+        None => "unresolved".to_string()
+    };
+
+    html! {
+        <footer class="container-fluid py-2 border-top text-body-secondary small">
+            <i class="bi bi-bug px-2"></i>{format!("API endpoint : {endpoint}")}
+        </footer>
+    }
+}
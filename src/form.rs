@@ -1,27 +1,27 @@
-use std::cell::RefCell;
+use std::cell::Cell;
 use std::rc::Rc;
 
-use serde::Serialize;
+use gloo_timers::callback::Timeout;
 use yew::prelude::*;
 
+use crate::api::{ApiClient, SnowballParameters, SnowballUpdate};
 use crate::common::{self, SearchFor, get_value};
+use crate::cache::{self, CacheEntry};
+use crate::config::Endpoint;
 
-use crate::table::article::Article;
+use crate::table::SearchResults;
 use crate::common::*;
 
+const DEFAULT_TIMEOUT_SECS: u32 = 300;
+
 #[derive(Clone, PartialEq, Properties)]
 pub struct FormProps {
+    pub endpoint: Endpoint,
+    pub config_loaded: bool,
     pub on_submit_error: Callback<common::Error>,
     pub on_requesting_table: Callback<()>,
-    pub on_receiving_response: Callback<Result<Rc<RefCell<Vec<Article>>>, Error>>,
-}
-
-#[derive(Clone, PartialEq, Properties, Debug, Default, Serialize)]
-struct SnowballParameters {
-    output_max_size: usize,
-    depth: u8,
-    input_id_list: Vec<String>,
-    search_for: common::SearchFor
+    pub on_update: Callback<SnowballUpdate>,
+    pub on_receiving_response: Callback<Result<SearchResults, Error>>,
 }
 
 impl SnowballParameters {
@@ -61,33 +61,36 @@ impl SnowballParameters {
     }
 }
 
-async fn get_response(form_content: &SnowballParameters) -> Result<Rc<RefCell<Vec<Article>>>, Error> {
-    use gloo_utils::document;
-    let url = document().document_uri();
-    let url = match url {
-        Ok(href) => Ok(href),
-        Err(err) => Err(Error::JsValueString(err.as_string().unwrap_or_default()))
-    }?.replace('#', "");
-
-    let mut api_url = url::Url::parse(&url)?;
-    api_url.set_fragment("".into());
-    api_url.set_query("".into());
-    api_url.set_path("api");
-
-    let response = gloo_net::http::Request::post(api_url.as_str())
-        .header("Access-Control-Allow-Origin", "*")
-        .body(serde_json::to_string(&form_content)?)?
-        .send()
-        .await?
-        .text()
-        .await?;
-
-    let value = serde_json::from_str::<serde_json::Value>(&response)?;
-    let mut articles = serde_json::from_value::<Vec<Article>>(value)?;
+async fn get_response(endpoint: Endpoint,
+        form_content: &SnowballParameters,
+        abort_signal: &web_sys::AbortSignal,
+        on_update: &dyn Fn(SnowballUpdate),
+        force_refresh: bool) -> Result<SearchResults, Error> {
+    let key = cache::cache_key(&endpoint.url, form_content);
+    let cached = match force_refresh {
+        true => None,
+        false => cache::get(&key).await.unwrap_or_else(|error| {
+            gloo_console::log!(format!("{error}"));
+            None
+        })
+    };
 
-    articles.sort_by_key(|article| std::cmp::Reverse(article.score.unwrap_or_default()));
-    
-    Ok(Rc::new(RefCell::new(articles)))
+    let (results, cached_at_ms) = match cached {
+        Some(entry) => (entry.results, Some(entry.created_at_ms)),
+        None => {
+            let results = ApiClient::new(endpoint.url)
+                .snowball(form_content, Some(abort_signal), on_update)
+                .await?;
+            let entry = CacheEntry { key, created_at_ms: js_sys::Date::now(), parameters: form_content.clone(), results };
+            if let Err(error) = cache::put(&entry).await {
+                gloo_console::log!(format!("{error}"));
+            }
+            (entry.results, None)
+        }
+    };
+    let mut results = SearchResults::new(results, form_content.input_id_list.len(), cached_at_ms);
+    results.parameters = Some(form_content.clone());
+    Ok(results)
 }
 
 fn id_list_prefill() -> Option<String> {
@@ -107,12 +110,30 @@ fn id_list_prefill() -> Option<String> {
     Some(id_list_prefill)
 }
 
+struct InFlightSearch {
+    abort_controller: web_sys::AbortController,
+    _timeout: Timeout
+}
+
+impl InFlightSearch {
+    fn abort(self) {
+        self.abort_controller.abort();
+    }
+}
+
 #[function_component]
 pub fn SnowballForm(props: &FormProps) -> Html {
     let id_list_node = use_node_ref();
     let depth_node = use_node_ref();
     let output_max_size_node = use_node_ref();
     let search_for_node = use_node_ref();
+    let timeout_node = use_node_ref();
+    let force_refresh_node = use_node_ref();
+
+    // Only the response of the search numbered `latest_search` is forwarded, older ones are either aborted or superseded
+    let latest_search = use_mut_ref(|| 0u64);
+    let in_flight_search = use_mut_ref(|| None::<InFlightSearch>);
+    let searching = use_state(|| false);
     
     let id_list = use_state(|| id_list_prefill().unwrap_or_default());
 
@@ -135,9 +156,24 @@ pub fn SnowballForm(props: &FormProps) -> Html {
         let on_submit_error = props.on_submit_error.clone();
         let on_receiving_response = props.on_receiving_response.clone();
         let on_requesting_table = props.on_requesting_table.clone();
+        let on_update = props.on_update.clone();
+        let endpoint = props.endpoint.clone();
+        let timeout_node = timeout_node.clone();
+        let force_refresh_node = force_refresh_node.clone();
+        let latest_search = latest_search.clone();
+        let in_flight_search = in_flight_search.clone();
+        let searching = searching.clone();
+        let config_loaded = props.config_loaded;
         
         Callback::from(move |event: SubmitEvent| {
             event.prevent_default();
+            if !config_loaded {
+                return;
+            }
+            if let Some(previous_search) = in_flight_search.borrow_mut().take() {
+                previous_search.abort();
+            }
+            *latest_search.borrow_mut() += 1;
             on_requesting_table.emit(());
             
             let form_content = SnowballParameters::new(id_list_node.clone(),
@@ -148,58 +184,367 @@ pub fn SnowballForm(props: &FormProps) -> Html {
             let form_content = match form_content {
                 Ok(form_content) => form_content,
                 Err(error) => {
+                    searching.set(false);
                     on_submit_error.emit(error);
                     return
                 }
             };
             
+            let timeout_secs = get_value(&timeout_node)
+                .and_then(|value| value.parse::<u32>().ok())
+                .unwrap_or(DEFAULT_TIMEOUT_SECS);
+            let abort_controller = match web_sys::AbortController::new() {
+                Ok(abort_controller) => abort_controller,
+                Err(error) => {
+                    searching.set(false);
+                    on_submit_error.emit(error.into());
+                    return
+                }
+            };
+            let force_refresh = force_refresh_node
+                .cast::<web_sys::HtmlInputElement>()
+                .is_some_and(|input| input.checked());
+            let timed_out = Rc::new(Cell::new(false));
+            let timeout = {
+                let abort_controller = abort_controller.clone();
+                let timed_out = timed_out.clone();
+                Timeout::new(timeout_secs * 1000, move || {
+                    timed_out.set(true);
+                    abort_controller.abort();
+                })
+            };
+            let abort_signal = abort_controller.signal();
+            *in_flight_search.borrow_mut() = Some(InFlightSearch { abort_controller, _timeout: timeout });
+            searching.set(true);
+
+            let search_number = *latest_search.borrow();
+            let on_update = {
+                let on_update = on_update.clone();
+                let latest_search = latest_search.clone();
+                move |update: SnowballUpdate| {
+                    if *latest_search.borrow() == search_number {
+                        on_update.emit(update);
+                    }
+                }
+            };
             let on_receiving_response = on_receiving_response.clone();
+            let endpoint = endpoint.clone();
+            let latest_search = latest_search.clone();
+            let in_flight_search = in_flight_search.clone();
+            let searching = searching.clone();
             wasm_bindgen_futures::spawn_local(async move {
-                let response = get_response(&form_content).await;
+                let response = get_response(endpoint, &form_content, &abort_signal, &on_update, force_refresh).await;
+                if *latest_search.borrow() != search_number {
+                    return;
+                }
+                in_flight_search.borrow_mut().take();
+                searching.set(false);
+
+                let response = match response {
+                    Err(_) if timed_out.get() => Err(Error::TimedOut(timeout_secs)),
+                    response => response
+                };
                 on_receiving_response.emit(response);
             });
         })
     };
+
+    let oncancel = {
+        let on_receiving_response = props.on_receiving_response.clone();
+        let latest_search = latest_search.clone();
+        let in_flight_search = in_flight_search.clone();
+        let searching = searching.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(search) = in_flight_search.borrow_mut().take() {
+                search.abort();
+            }
+            *latest_search.borrow_mut() += 1;
+            searching.set(false);
+            on_receiving_response.emit(Err(Error::Cancelled));
+        })
+    };
     
     html! {
-        <form class="container-md" onsubmit={onsubmit} style={"margin-bottom: 50px;"}>
-            <div class="mb-3 form-check">
-                <label for="idInput" class="form-label">{"Enter a list of PMIDs, DOIs or Lens IDs"}</label>
-                <input type="text" class="form-control" id="idInput" {onchange} ref={id_list_node.clone()} value={id_list.to_string()}/>
-                <div id="idInputHelp" class="form-text">{"You can enter multiple references separated by spaces."}</div>
-            </div>
-            <div class="mb-3 form-check">
-                <div class="row">
-                <div class="col">
-                    <label class="form-check-label" for="depthSelect">{"Select depth"}</label>
-                    <select class="form-select" aria-label="Default select example" id="depthSelect" value="2" ref={depth_node.clone()}>
-                        <option value="1">{"1"}</option>
-                        <option value="2" selected=true>{"2"}</option>
+        <form class="container-md" id="snowballForm" onsubmit={onsubmit} style={"margin-bottom: 50px;"}>
+            <fieldset disabled={!props.config_loaded}>
+                <div class="mb-3 form-check">
+                    <label for="idInput" class="form-label">{"Enter a list of PMIDs, DOIs or Lens IDs"}</label>
+                    <input type="text" class="form-control" id="idInput" {onchange} ref={id_list_node.clone()} value={id_list.to_string()}/>
+                    <div id="idInputHelp" class="form-text">{"You can enter multiple references separated by spaces."}</div>
+                </div>
+                <div class="mb-3 form-check">
+                    <div class="row">
+                    <div class="col">
+                        <label class="form-check-label" for="depthSelect">{"Select depth"}</label>
+                        <select class="form-select" aria-label="Default select example" id="depthSelect" value="2" ref={depth_node.clone()}>
+                            <option value="1">{"1"}</option>
+                            <option value="2" selected=true>{"2"}</option>
+                        </select>
+                        <div id="depthSelectHelp" class="form-text">{"The recommended depth value is 2"}</div>
+                    </div>
+                    <div class="col">
+                        <label class="form-check-label" for="maxOutputSizeSelect">{"Number of results"}</label>
+                        <select class="form-select" aria-label="Default select example" id="maxOutputSizeSelect" value="100" ref={output_max_size_node.clone()}>
+                            <option value="100" selected=true>{"100"}</option>
+                            <option value="500">{"500"}</option>
+                            <option value="1000">{"1000"}</option>
+                        </select>
+                    </div>
+                    </div>
+                </div>
+                <div class="mb-3 form-check">
+                    <label class="form-check-label" for="searchForSelect">{"Search direction"}</label>
+                    <select class="form-select" aria-label="Default select example" id="searchForSelect" ref={search_for_node.clone()}>
+                        <option value="Both" selected=true>{"Both"}</option>
+                        <option value="Citations">{"Citations"}</option>
+                        <option value="References">{"References"}</option>
                     </select>
-                    <div id="depthSelectHelp" class="form-text">{"The recommended depth value is 2"}</div>
+                    <div id="searchForSelectHelp" class="form-text">{"For most cases, we recommend Both"}</div>
                 </div>
-                <div class="col">
-                    <label class="form-check-label" for="maxOutputSizeSelect">{"Number of results"}</label>
-                    <select class="form-select" aria-label="Default select example" id="maxOutputSizeSelect" value="100" ref={output_max_size_node.clone()}>
-                        <option value="100" selected=true>{"100"}</option>
-                        <option value="500">{"500"}</option>
-                        <option value="1000">{"1000"}</option>
+                <div class="mb-3 form-check">
+                    <label class="form-check-label" for="timeoutSelect">{"Timeout"}</label>
+                    <select class="form-select" id="timeoutSelect" ref={timeout_node.clone()}>
+                        <option value="60">{"1 minute"}</option>
+                        <option value="120">{"2 minutes"}</option>
+                        <option value="300" selected=true>{"5 minutes"}</option>
+                        <option value="600">{"10 minutes"}</option>
                     </select>
+                    <div id="timeoutSelectHelp" class="form-text">{"Searches with depth 2 and 1000 results can take several minutes"}</div>
+                </div>
+                <div class="mb-3 form-check">
+                    <input class="form-check-input ms-0 me-2" type="checkbox" id="forceRefreshCheck" ref={force_refresh_node.clone()}/>
+                    <label class="form-check-label" for="forceRefreshCheck">{"Force refresh"}</label>
+                    <div id="forceRefreshCheckHelp" class="form-text">{"Ignore the results of identical searches cached in your browser"}</div>
                 </div>
+                <div class="text-center">
+                    <button type="submit" class="btn btn-outline-secondary btn-lg">{"Search for related articles"}</button>
+                    if !props.config_loaded {
+                        <div class="form-text">{"Loading the server configuration…"}</div>
+                    }
+                    if *searching {
+                        <button type="button" class="btn btn-outline-danger btn-lg ms-2" onclick={oncancel}><i class="bi bi-x-circle me-2"></i>{"Cancel"}</button>
+                    }
                 </div>
-            </div>
-            <div class="mb-3 form-check">
-                <label class="form-check-label" for="searchForSelect">{"Search direction"}</label>
-                <select class="form-select" aria-label="Default select example" id="searchForSelect" ref={search_for_node.clone()}>
-                    <option value="Both" selected=true>{"Both"}</option>
-                    <option value="Citations">{"Citations"}</option>
-                    <option value="References">{"References"}</option>
-                </select>
-                <div id="searchForSelectHelp" class="form-text">{"For most cases, we recommend Both"}</div>
-            </div>
-            <div class="text-center">
-                <button type="submit" class="btn btn-outline-secondary btn-lg">{"Search for related articles"}</button>
-            </div>
+            </fieldset>
         </form>
     }
 }
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use std::cell::RefCell;
+
+    use gloo_timers::future::TimeoutFuture;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_test::*;
+    use yew::AppHandle;
+
+    use super::*;
+    use crate::config::EndpointSource;
+    use crate::table::{StatusCallbacks, TableContainer, TableStatus};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    const POLL_INTERVAL_MS: u32 = 50;
+    // Long enough for the retries of the transient errors
+    const WAIT_LIMIT_MS: u32 = 20_000;
+
+    fn label(status: &TableStatus) -> &'static str {
+        match status {
+            TableStatus::NotRequested => "NotRequested",
+            TableStatus::Requested(_) => "Requested",
+            TableStatus::Retrying(_) => "Retrying",
+            TableStatus::RequestError(_) => "RequestError",
+            TableStatus::Available(_) => "Available"
+        }
+    }
+
+    #[derive(Clone, PartialEq, Properties)]
+    struct HarnessProps {
+        endpoint: Endpoint,
+        statuses: Rc<RefCell<Vec<&'static str>>>
+    }
+
+    // BibliZapApp recording every status set
+    #[function_component]
+    fn Harness(props: &HarnessProps) -> Html {
+        let table_status = use_state(|| TableStatus::NotRequested);
+        let set_status = {
+            let table_status = table_status.clone();
+            let statuses = props.statuses.clone();
+            Callback::from(move |status: TableStatus| {
+                statuses.borrow_mut().push(label(&status));
+                table_status.set(status);
+            })
+        };
+
+        let StatusCallbacks { on_submit_error, on_requesting_table, on_update, on_receiving_response } = StatusCallbacks::new(set_status);
+
+        html! {
+            <>
+                <SnowballForm endpoint={props.endpoint.clone()} config_loaded=true {on_submit_error} {on_requesting_table} {on_update} {on_receiving_response}/>
+                <TableContainer {table_status}/>
+            </>
+        }
+    }
+
+    struct Mounted {
+        root: web_sys::Element,
+        statuses: Rc<RefCell<Vec<&'static str>>>,
+        app: AppHandle<Harness>
+    }
+
+    impl Mounted {
+        async fn new(scenario: &str) -> Self {
+            let root = gloo_utils::document().create_element("div").unwrap();
+            gloo_utils::body().append_child(&root).unwrap();
+            let statuses = Rc::new(RefCell::new(Vec::new()));
+            let endpoint = Endpoint {
+                url: url::Url::parse(&format!("mock:{scenario}")).unwrap(),
+                source: EndpointSource::QueryParameter
+            };
+            let props = HarnessProps { endpoint, statuses: statuses.clone() };
+            let app = yew::Renderer::<Harness>::with_root_and_props(root.clone(), props).render();
+            TimeoutFuture::new(0).await;
+            Mounted { root, statuses, app }
+        }
+
+        fn element<T: JsCast>(&self, selector: &str) -> Option<T> {
+            self.root.query_selector(selector).unwrap().and_then(|element| element.dyn_into::<T>().ok())
+        }
+
+        fn statuses(&self) -> Vec<&'static str> {
+            self.statuses.borrow().clone()
+        }
+
+        fn last_status(&self) -> Option<&'static str> {
+            self.statuses.borrow().last().copied()
+        }
+
+        fn rows(&self) -> u32 {
+            self.root.query_selector_all("#table tbody tr").unwrap().length()
+        }
+
+        fn text(&self) -> String {
+            self.root.text_content().unwrap_or_default()
+        }
+
+        fn submit(&self) {
+            self.element::<web_sys::HtmlInputElement>("#idInput").unwrap().set_value("10.1148/radiol.2020201365 32109443");
+            self.element::<web_sys::HtmlInputElement>("#forceRefreshCheck").unwrap().set_checked(true);
+            self.element::<web_sys::HtmlFormElement>("#snowballForm").unwrap().request_submit().unwrap();
+        }
+
+        async fn wait_until(&self, description: &str, condition: impl Fn(&Self) -> bool) {
+            let mut waited = 0;
+            while !condition(self) {
+                assert!(waited < WAIT_LIMIT_MS, "timed out waiting for {description}, statuses: {:?}", self.statuses());
+                TimeoutFuture::new(POLL_INTERVAL_MS).await;
+                waited += POLL_INTERVAL_MS;
+            }
+        }
+
+        fn unmount(self) {
+            self.app.destroy();
+            self.root.remove();
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn ok_search_shows_every_article() {
+        let mounted = Mounted::new("ok").await;
+        mounted.submit();
+        mounted.wait_until("the rows", |mounted| mounted.rows() > 0).await;
+
+        assert_eq!(mounted.statuses(), ["Requested", "Available"]);
+        assert_eq!(mounted.rows(), 3);
+        assert!(mounted.text().contains("All 2 seeds were resolved"));
+        mounted.unmount();
+    }
+
+    #[wasm_bindgen_test]
+    async fn bad_request_shows_the_error() {
+        let mounted = Mounted::new("bad-request").await;
+        mounted.submit();
+        mounted.wait_until("the error", |mounted| mounted.last_status() == Some("RequestError")).await;
+        TimeoutFuture::new(POLL_INTERVAL_MS).await;
+
+        assert_eq!(mounted.statuses(), ["Requested", "RequestError"]);
+        assert!(mounted.text().contains("The server rejected the search"));
+        assert_eq!(mounted.rows(), 0);
+        mounted.unmount();
+    }
+
+    #[wasm_bindgen_test]
+    async fn server_error_is_retried_then_shown() {
+        let mounted = Mounted::new("server-error").await;
+        mounted.submit();
+        mounted.wait_until("the error", |mounted| mounted.last_status() == Some("RequestError")).await;
+        TimeoutFuture::new(POLL_INTERVAL_MS).await;
+
+        assert_eq!(mounted.statuses(), ["Requested", "Retrying", "Retrying", "Retrying", "RequestError"]);
+        assert!(mounted.text().contains("The BibliZap server failed to complete the search"));
+        assert_eq!(mounted.rows(), 0);
+        mounted.unmount();
+    }
+
+    #[wasm_bindgen_test]
+    async fn rate_limited_search_succeeds_after_retrying() {
+        let mounted = Mounted::new("rate-limited").await;
+        mounted.submit();
+        mounted.wait_until("the retry notice", |mounted| mounted.text().contains("Retrying (1/3)")).await;
+        mounted.wait_until("the rows", |mounted| mounted.rows() > 0).await;
+
+        assert_eq!(mounted.statuses(), ["Requested", "Retrying", "Retrying", "Available"]);
+        assert_eq!(mounted.rows(), 3);
+        mounted.unmount();
+    }
+
+    #[wasm_bindgen_test]
+    async fn unresolved_seeds_are_reported_along_with_the_rows() {
+        let mounted = Mounted::new("unresolved").await;
+        mounted.submit();
+        mounted.wait_until("the rows", |mounted| mounted.rows() > 0).await;
+
+        assert_eq!(mounted.statuses(), ["Requested", "Available"]);
+        assert_eq!(mounted.rows(), 1);
+        assert!(mounted.text().contains("1 of 2 seeds could not be resolved"));
+        assert!(mounted.text().contains("Unknown to The Lens"));
+        mounted.unmount();
+    }
+
+    #[wasm_bindgen_test]
+    async fn slow_search_can_be_cancelled() {
+        let mounted = Mounted::new("slow").await;
+        mounted.submit();
+        mounted.wait_until("the cancel button", |mounted| mounted.element::<web_sys::HtmlElement>("button.btn-outline-danger").is_some()).await;
+        mounted.element::<web_sys::HtmlElement>("button.btn-outline-danger").unwrap().click();
+        mounted.wait_until("the cancellation", |mounted| mounted.last_status() == Some("NotRequested")).await;
+        // The aborted response must not be shown once it settles
+        TimeoutFuture::new(500).await;
+
+        assert_eq!(mounted.statuses(), ["Requested", "NotRequested"]);
+        assert!(mounted.element::<web_sys::HtmlElement>("button.btn-outline-danger").is_none());
+        assert_eq!(mounted.rows(), 0);
+        mounted.unmount();
+    }
+
+    #[wasm_bindgen_test]
+    async fn slow_search_times_out() {
+        let mounted = Mounted::new("slow").await;
+        // A one second timeout, appended already selected to the options offered
+        let option = gloo_utils::document().create_element("option").unwrap();
+        option.set_attribute("value", "1").unwrap();
+        option.set_attribute("selected", "").unwrap();
+        mounted.element::<web_sys::Element>("#timeoutSelect").unwrap().append_child(&option).unwrap();
+
+        mounted.submit();
+        mounted.wait_until("the timeout", |mounted| mounted.last_status() == Some("RequestError")).await;
+        TimeoutFuture::new(POLL_INTERVAL_MS).await;
+
+        assert_eq!(mounted.statuses(), ["Requested", "RequestError"]);
+        assert!(mounted.text().contains("The search took too long to complete"));
+        assert_eq!(mounted.rows(), 0);
+        mounted.unmount();
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::api::EdgeKind;
+use crate::table::SearchResults;
+
+const WIDTH: f64 = 1000.0;
+const HEIGHT: f64 = 700.0;
+const LAYOUT_ITERATIONS: usize = 300;
+/// Only the best scored articles are drawn, the layout being quadratic in the number of nodes
+const MAX_ARTICLE_NODES: usize = 200;
+const MIN_NODE_RADIUS: f64 = 4.0;
+const MAX_NODE_RADIUS: f64 = 20.0;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct GraphNode {
+    pub lens_id: String,
+    pub article_id: Option<usize>,
+    pub label: String,
+    pub score: i32,
+    pub is_seed: bool,
+    pub x: f64,
+    pub y: f64
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct GraphEdge {
+    pub source: usize,
+    pub target: usize,
+    pub kind: EdgeKind
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Graph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>
+}
+
+impl Graph {
+    pub fn new(results: &SearchResults) -> Self {
+        let mut nodes = Vec::<GraphNode>::new();
+        let mut node_index = HashMap::<String, usize>::new();
+
+        for seed in results.seeds.iter() {
+            let Some(lens_id) = seed.lens_id.clone() else { continue };
+            node_index.insert(lens_id.clone(), nodes.len());
+            nodes.push(GraphNode {
+                lens_id,
+                article_id: None,
+                label: seed.title.clone().unwrap_or(seed.input.clone()),
+                score: 0,
+                is_seed: true,
+                x: 0.0,
+                y: 0.0
+            });
+        }
+
+        let mut articles = results.articles
+            .borrow()
+            .iter()
+            .filter(|article| article.lens_id.is_some())
+            .cloned()
+            .collect::<Vec<_>>();
+        articles.sort_by_key(|article| std::cmp::Reverse(article.score.unwrap_or_default()));
+
+        for article in articles.into_iter().take(MAX_ARTICLE_NODES) {
+            let Some(lens_id) = article.lens_id.clone() else { continue };
+            let label = article.title.clone().unwrap_or(lens_id.clone());
+            match node_index.get(&lens_id) {
+                Some(index) => {
+                    let seed = &mut nodes[*index];
+                    seed.article_id = Some(article.id);
+                    seed.score = article.score.unwrap_or_default();
+                },
+                None => {
+                    node_index.insert(lens_id.clone(), nodes.len());
+                    nodes.push(GraphNode {
+                        lens_id,
+                        article_id: Some(article.id),
+                        label,
+                        score: article.score.unwrap_or_default(),
+                        is_seed: false,
+                        x: 0.0,
+                        y: 0.0
+                    });
+                }
+            }
+        }
+
+        let edges = results.edges
+            .iter()
+            .filter_map(|edge| Some(GraphEdge {
+                source: *node_index.get(&edge.source)?,
+                target: *node_index.get(&edge.target)?,
+                kind: edge.kind
+            }))
+            .collect::<Vec<_>>();
+
+        let mut graph = Graph { nodes, edges };
+        graph.layout(LAYOUT_ITERATIONS);
+        graph
+    }
+
+    /// Fruchterman-Reingold force-directed layout, starting from a spiral so that it is deterministic
+    fn layout(&mut self, iterations: usize) {
+        let n = self.nodes.len();
+        if n == 0 {
+            return;
+        }
+
+        let (center_x, center_y) = (WIDTH / 2.0, HEIGHT / 2.0);
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            let angle = i as f64 * 2.399_963; // Golden angle
+            let radius = 10.0 * (i as f64).sqrt();
+            node.x = center_x + radius * angle.cos();
+            node.y = center_y + radius * angle.sin();
+        }
+
+        let k = (WIDTH * HEIGHT / n as f64).sqrt();
+        let mut displacements = vec![(0.0f64, 0.0f64); n];
+        for iteration in 0..iterations {
+            let temperature = WIDTH / 10.0 * (1.0 - iteration as f64 / iterations as f64);
+            displacements.iter_mut().for_each(|d| *d = (0.0, 0.0));
+
+            for i in 0..n {
+                for j in (i + 1)..n {
+                    let dx = self.nodes[i].x - self.nodes[j].x;
+                    let dy = self.nodes[i].y - self.nodes[j].y;
+                    let distance = (dx*dx + dy*dy).sqrt().max(0.01);
+                    let force = k * k / distance;
+                    displacements[i].0 += dx / distance * force;
+                    displacements[i].1 += dy / distance * force;
+                    displacements[j].0 -= dx / distance * force;
+                    displacements[j].1 -= dy / distance * force;
+                }
+            }
+
+            for edge in self.edges.iter() {
+                let dx = self.nodes[edge.source].x - self.nodes[edge.target].x;
+                let dy = self.nodes[edge.source].y - self.nodes[edge.target].y;
+                let distance = (dx*dx + dy*dy).sqrt().max(0.01);
+                let force = distance * distance / k;
+                displacements[edge.source].0 -= dx / distance * force;
+                displacements[edge.source].1 -= dy / distance * force;
+                displacements[edge.target].0 += dx / distance * force;
+                displacements[edge.target].1 += dy / distance * force;
+            }
+
+            for (node, (dx, dy)) in self.nodes.iter_mut().zip(displacements.iter()) {
+                // Gravity keeps disconnected components on screen
+                let dx = dx - (node.x - center_x) * 0.05;
+                let dy = dy - (node.y - center_y) * 0.05;
+                let length = (dx*dx + dy*dy).sqrt().max(0.01);
+                node.x = (node.x + dx / length * length.min(temperature)).clamp(0.0, WIDTH);
+                node.y = (node.y + dy / length * length.min(temperature)).clamp(0.0, HEIGHT);
+            }
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct CitationGraphProps {
+    pub results: SearchResults,
+    pub on_select: Callback<usize>
+}
+
+#[function_component(CitationGraph)]
+pub fn citation_graph(props: &CitationGraphProps) -> Html {
+    let graph = {
+        let results = props.results.clone();
+        // Merging duplicates removes articles in place
+        let articles_key = (Rc::as_ptr(&props.results.articles) as usize, props.results.articles.borrow().len());
+        use_memo(articles_key, move |_| Graph::new(&results))
+    };
+
+    let scale = use_state(|| 1.0f64);
+    let offset = use_state(|| (0.0f64, 0.0f64));
+    let drag_origin = use_mut_ref(|| None::<(i32, i32, f64, f64)>);
+
+    let onwheel = {
+        let scale = scale.clone();
+        Callback::from(move |event: WheelEvent| {
+            event.prevent_default();
+            let factor = if event.delta_y() < 0.0 { 1.1 } else { 1.0 / 1.1 };
+            scale.set((*scale * factor).clamp(0.2, 10.0));
+        })
+    };
+    let onmousedown = {
+        let drag_origin = drag_origin.clone();
+        let offset = offset.clone();
+        Callback::from(move |event: MouseEvent| {
+            *drag_origin.borrow_mut() = Some((event.client_x(), event.client_y(), offset.0, offset.1));
+        })
+    };
+    let onmousemove = {
+        let drag_origin = drag_origin.clone();
+        let offset = offset.clone();
+        let scale = scale.clone();
+        Callback::from(move |event: MouseEvent| {
+            if let Some((x, y, offset_x, offset_y)) = *drag_origin.borrow() {
+                offset.set((offset_x + (event.client_x() - x) as f64 / *scale, offset_y + (event.client_y() - y) as f64 / *scale));
+            }
+        })
+    };
+    let onmouseup = {
+        let drag_origin = drag_origin.clone();
+        Callback::from(move |_: MouseEvent| {
+            *drag_origin.borrow_mut() = None;
+        })
+    };
+    let onreset = {
+        let scale = scale.clone();
+        let offset = offset.clone();
+        Callback::from(move |_: MouseEvent| {
+            scale.set(1.0);
+            offset.set((0.0, 0.0));
+        })
+    };
+
+    if graph.edges.is_empty() {
+        return html! {
+            <div class="container-md alert alert-secondary">{"The BibliZap server did not report the citation network of this search."}</div>
+        };
+    }
+
+    let max_score = graph.nodes.iter().map(|node| node.score).max().unwrap_or(1).max(1) as f64;
+    let radius = |node: &GraphNode| MIN_NODE_RADIUS + (MAX_NODE_RADIUS - MIN_NODE_RADIUS) * (node.score.max(0) as f64 / max_score).sqrt();
+
+    let edges = graph.edges.iter().map(|edge| {
+        let (source, target) = (&graph.nodes[edge.source], &graph.nodes[edge.target]);
+        let dash = match edge.kind {
+            EdgeKind::Reference => "",
+            EdgeKind::Citation => "4 3"
+        };
+        html! {
+            <line x1={source.x.to_string()} y1={source.y.to_string()} x2={target.x.to_string()} y2={target.y.to_string()}
+                stroke="var(--bs-secondary-color)" stroke-opacity="0.5" stroke-dasharray={dash}/>
+        }
+    }).collect::<Html>();
+
+    let nodes = graph.nodes.iter().map(|node| {
+        let onclick = {
+            let on_select = props.on_select.clone();
+            let article_id = node.article_id;
+            Callback::from(move |event: MouseEvent| {
+                event.stop_propagation();
+                if let Some(article_id) = article_id {
+                    on_select.emit(article_id);
+                }
+            })
+        };
+        let fill = match node.is_seed {
+            true => "var(--bs-danger)",
+            false => "var(--bs-primary)"
+        };
+        html! {
+            <circle cx={node.x.to_string()} cy={node.y.to_string()} r={radius(node).to_string()} fill={fill} fill-opacity="0.8"
+                style={if node.article_id.is_some() { "cursor: pointer" } else { "" }} {onclick}>
+                <title>{format!("{} (score {})", node.label, node.score)}</title>
+            </circle>
+        }
+    }).collect::<Html>();
+
+    let transform = format!("translate({} {}) scale({}) translate({} {})", WIDTH / 2.0, HEIGHT / 2.0, *scale, offset.0 - WIDTH / 2.0, offset.1 - HEIGHT / 2.0);
+
+    html! {
+        <div class="container-fluid mb-3">
+            <div class="d-flex align-items-center gap-3 mb-2">
+                <span><i class="bi bi-circle-fill text-danger me-1"></i>{"Seed"}</span>
+                <span><i class="bi bi-circle-fill text-primary me-1"></i>{"Result, sized by score"}</span>
+                <span>{"Solid : reference, dashed : citation"}</span>
+                <button class="btn btn-outline-secondary btn-sm ms-auto" onclick={onreset}>{"Reset view"}</button>
+            </div>
+            <svg class="citation-graph border rounded" viewBox={format!("0 0 {WIDTH} {HEIGHT}")}
+                {onwheel} {onmousedown} {onmousemove} onmouseup={onmouseup.clone()} onmouseleave={onmouseup}>
+                <g transform={transform}>
+                    {edges}
+                    {nodes}
+                </g>
+            </svg>
+        </div>
+    }
+}
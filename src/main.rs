@@ -1,5 +1,4 @@
-use std::{ops::Deref, cell::RefCell};
-use std::rc::Rc;
+use std::ops::Deref;
 
 use yew::prelude::*;
 
@@ -18,8 +17,29 @@ use wall::*;
 mod form;
 use form::SnowballForm;
 
+mod api;
+
+mod graph;
+
+mod pool;
+use pool::CombineSearches;
+
+mod session;
+
+mod compare;
+use compare::CompareSearches;
+
+mod cache;
+use cache::CacheManagement;
+
+mod bench;
+use bench::Benchmark;
+
+mod config;
+use config::{DebugFooter, Endpoint};
+
 mod common;
-use common::{Error, CurrentPage};
+use common::{ErrorReport, RecoveryAction, CurrentPage};
 
 #[function_component(App)]
 fn app() -> Html {
@@ -30,50 +50,75 @@ fn app() -> Html {
         false => gloo_utils::document_element().set_attribute("data-bs-theme", "light").unwrap_or(())
     }
     
+    let endpoint = use_state(|| Endpoint::initial().ok());
+    // A query parameter overrides config.json, which is then not fetched
+    let config_loaded = use_state(|| matches!(endpoint.deref(), Some(Endpoint { source: config::EndpointSource::QueryParameter, .. })));
+    {
+        let endpoint = endpoint.clone();
+        let config_loaded = config_loaded.clone();
+        use_effect_with((), move |_| {
+            if !*config_loaded {
+                wasm_bindgen_futures::spawn_local(async move {
+                    match Endpoint::from_config_file().await {
+                        Ok(Some(from_config_file)) => endpoint.set(Some(from_config_file)),
+                        Ok(None) => (),
+                        Err(error) => gloo_console::log!(format!("Could not read config.json : {error}"))
+                    }
+                    config_loaded.set(true);
+                });
+            }
+        });
+    }
+    
     let content = match current_page.deref() {
-        CurrentPage::BibliZapApp => { html!{<BibliZapApp/>} },
+        CurrentPage::BibliZapApp => { html!{<BibliZapApp endpoint={endpoint.deref().clone()} config_loaded={*config_loaded}/>} },
         CurrentPage::HowItWorks => { html!{<HowItWorks/>} },
         CurrentPage::LegalInformation => { html!{<LegalInformation/>} },
-        CurrentPage::Contact => { html!{<Contact/>} }
+        CurrentPage::Contact => { html!{<Contact/>} },
+        CurrentPage::Cache => { html!{<CacheManagement/>} },
+        CurrentPage::Compare => { html!{<CompareSearches/>} },
+        CurrentPage::Benchmark => { html!{<Benchmark/>} }
     };
     html! {
         <div>
             <NavBar current_page={current_page} dark_mode={dark_mode}/>
             <Wall/>
             {content}
+            if cfg!(debug_assertions) {
+                <DebugFooter endpoint={endpoint.deref().clone()}/>
+            }
         </div>
     }
 }   
 
+#[derive(Clone, PartialEq, Properties)]
+struct BibliZapAppProps {
+    endpoint: Option<Endpoint>,
+    config_loaded: bool
+}
+
 #[function_component(BibliZapApp)]
-fn app() -> Html {
+fn app(props: &BibliZapAppProps) -> Html {
     let table_status = use_state(|| TableStatus::NotRequested);
-    let on_receiving_response = { 
-        let table_status = table_status.clone();
-        Callback::from(move |table: Result<Rc<RefCell<Vec<Article>>>, Error>| {
-            match table {
-                Ok(table) => table_status.set(TableStatus::Available(table)),
-                Err(error) => table_status.set(TableStatus::RequestError(error.to_string())),
-            };
-        })
-    };
-    let on_requesting_table = {
+    let StatusCallbacks { on_submit_error, on_requesting_table, on_update, on_receiving_response } = {
         let table_status = table_status.clone();
-        Callback::from(move |_: ()| {
-            table_status.set(TableStatus::Requested);
-        })
+        StatusCallbacks::new(Callback::from(move |status| table_status.set(status)))
     };
 
-    let on_submit_error= {
-        let table_status = table_status.clone();
-        Callback::from(move |error: common::Error| {
-            table_status.set(TableStatus::RequestError(error.to_string()))
-        })
+    let Some(endpoint) = props.endpoint.clone() else {
+        let report = ErrorReport {
+            explanation: "The address of the BibliZap server could not be determined.",
+            action: RecoveryAction::Reload,
+            details: String::new()
+        };
+        return html! {<table::Error {report}/>};
     };
 
     html! {
         <div>
-            <SnowballForm {on_submit_error} {on_requesting_table} {on_receiving_response}/>
+            <SnowballForm {endpoint} config_loaded={props.config_loaded} {on_submit_error} on_requesting_table={on_requesting_table.clone()} {on_update} on_receiving_response={on_receiving_response.clone()}/>
+            <CombineSearches on_requesting_table={on_requesting_table.clone()} on_receiving_response={on_receiving_response.clone()}/>
+            <table::import::ImportExport {on_requesting_table} {on_receiving_response}/>
             <TableContainer table_status={table_status.clone()}/>
         </div>
     }
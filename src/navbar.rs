@@ -40,6 +40,27 @@ pub fn NavBar(props: &NavBarProps) -> Html {
         })
     };
 
+    let onclick_cache = {
+        let current_page = props.current_page.clone();
+        Callback::from(move |_: MouseEvent| {
+            current_page.set(CurrentPage::Cache);
+        })
+    };
+
+    let onclick_compare = {
+        let current_page = props.current_page.clone();
+        Callback::from(move |_: MouseEvent| {
+            current_page.set(CurrentPage::Compare);
+        })
+    };
+
+    let onclick_benchmark = {
+        let current_page = props.current_page.clone();
+        Callback::from(move |_: MouseEvent| {
+            current_page.set(CurrentPage::Benchmark);
+        })
+    };
+
     let toggle_dark_mode = {
         let dark_mode = props.dark_mode.clone();
         Callback::from(move |_: MouseEvent| {
@@ -95,7 +116,36 @@ pub fn NavBar(props: &NavBarProps) -> Html {
                         {"Legal information"}
                         </a>
                     </li>
+                    <li class="nav-item" onclick={onclick_cache}>
+                        <a class={match props.current_page.deref() {
+                            CurrentPage::Cache => {"nav-link active"},
+                            _ => {"nav-link"}
+                        }}  href="#">
+                        <i class="bi bi-database px-2"></i>
+                        {"Cache"}
+                        </a>
+                    </li>
+                    <li class="nav-item" onclick={onclick_compare}>
+                        <a class={match props.current_page.deref() {
+                            CurrentPage::Compare => {"nav-link active"},
+                            _ => {"nav-link"}
+                        }}  href="#">
+                        <i class="bi bi-arrow-left-right px-2"></i>
+                        {"Compare"}
+                        </a>
+                    </li>
                     <BrowserPluginNavItem/>
+                    if cfg!(debug_assertions) {
+                        <li class="nav-item" onclick={onclick_benchmark}>
+                            <a class={match props.current_page.deref() {
+                                CurrentPage::Benchmark => {"nav-link active"},
+                                _ => {"nav-link"}
+                            }}  href="#">
+                            <i class="bi bi-speedometer px-2"></i>
+                            {"Benchmark"}
+                            </a>
+                        </li>
+                    }
                     <li class="nav-item" onclick={toggle_dark_mode}>
                         <button class="nav-link active">
                         if *props.dark_mode.deref() {
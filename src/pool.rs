@@ -0,0 +1,254 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Deref;
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::api::SnowballResults;
+use crate::cache::{self, CacheEntrySummary};
+use crate::common::Error;
+use crate::table::{Article, SearchResults};
+use crate::table::duplicates::fill_missing_fields;
+
+pub fn source_label(index: usize) -> String {
+    format!("S{}", index + 1)
+}
+
+/// A merged article scores the sum over the searches of its best score in each, and keeps the labels of the searches which found it
+pub fn combine(searches: Vec<(String, SnowballResults)>) -> SnowballResults {
+    let mut pool = Vec::<Article>::new();
+    let mut best_scores = Vec::<HashMap<String, i32>>::new();
+    let mut by_identifier = HashMap::<String, usize>::new();
+    let mut combined = SnowballResults::default();
+    let mut known_edges = HashSet::new();
+
+    for (label, results) in searches {
+        for mut article in results.articles {
+            let identifiers = article.identifiers();
+            let score = article.score;
+            let index = match identifiers.iter().find_map(|id| by_identifier.get(id).copied()) {
+                Some(index) => {
+                    let pooled = &mut pool[index];
+                    fill_missing_fields(pooled, &article);
+                    let sources = pooled.sources.get_or_insert_with(Vec::new);
+                    if !sources.contains(&label) {
+                        sources.push(label.clone());
+                    }
+                    for id in pooled.identifiers() {
+                        by_identifier.insert(id, index);
+                    }
+                    index
+                },
+                None => {
+                    article.sources = Some(vec![label.clone()]);
+                    for id in identifiers {
+                        by_identifier.insert(id, pool.len());
+                    }
+                    pool.push(article);
+                    best_scores.push(HashMap::new());
+                    pool.len() - 1
+                }
+            };
+            if let Some(score) = score {
+                let best = best_scores[index].entry(label.clone()).or_insert(score);
+                *best = (*best).max(score);
+            }
+        }
+
+        for seed in results.seeds {
+            if !combined.seeds.iter().any(|known| known.input == seed.input) {
+                combined.seeds.push(seed);
+            }
+        }
+        for edge in results.edges {
+            if known_edges.insert((edge.source.clone(), edge.target.clone(), edge.kind)) {
+                combined.edges.push(edge);
+            }
+        }
+    }
+
+    for (article, best_scores) in pool.iter_mut().zip(best_scores) {
+        article.score = (!best_scores.is_empty()).then(|| best_scores.values().sum());
+        article.max_score = best_scores.into_values().max();
+    }
+    combined.articles = pool;
+    combined
+}
+
+fn describe(label: &str, summary: &CacheEntrySummary) -> String {
+    format!("{label} : {} (depth {}, {:?})", summary.parameters.input_id_list.join(" "), summary.parameters.depth, summary.parameters.search_for)
+}
+
+async fn load_combined(summaries: Vec<CacheEntrySummary>) -> Result<SearchResults, Error> {
+    let mut searches = Vec::new();
+    let mut descriptions = Vec::new();
+    let mut input_ids = Vec::new();
+    for (index, summary) in summaries.iter().enumerate() {
+        let Some(entry) = cache::get(&summary.key).await? else {
+            return Err(Error::Cache(format!("The search {} expired from the cache", summary.parameters.input_id_list.join(" "))));
+        };
+        let label = source_label(index);
+        descriptions.push(describe(&label, summary));
+        input_ids.extend(entry.parameters.input_id_list.iter().map(|id| id.trim().to_lowercase()));
+        searches.push((label, entry.results));
+    }
+    input_ids.sort();
+    input_ids.dedup();
+
+    let mut results = SearchResults::new(combine(searches), input_ids.len(), None);
+    results.sources = Rc::new(descriptions);
+    Ok(results)
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct CombineSearchesProps {
+    pub on_requesting_table: Callback<()>,
+    pub on_receiving_response: Callback<Result<SearchResults, Error>>
+}
+
+#[function_component(CombineSearches)]
+pub fn combine_searches(props: &CombineSearchesProps) -> Html {
+    let summaries = use_state(|| None::<Result<Vec<CacheEntrySummary>, String>>);
+    let selected = use_state(Vec::<String>::new);
+
+    let ontoggle = {
+        let summaries = summaries.clone();
+        let selected = selected.clone();
+        Callback::from(move |_: MouseEvent| {
+            if summaries.is_some() {
+                summaries.set(None);
+                return;
+            }
+            selected.set(Vec::new());
+            let summaries = summaries.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let loaded = cache::summaries().await
+                    .map(|all| all.into_iter().filter(|summary| !summary.expired).collect())
+                    .map_err(|error| error.to_string());
+                summaries.set(Some(loaded));
+            });
+        })
+    };
+
+    let oncombine = {
+        let summaries = summaries.clone();
+        let selected = selected.clone();
+        let on_requesting_table = props.on_requesting_table.clone();
+        let on_receiving_response = props.on_receiving_response.clone();
+        Callback::from(move |_: MouseEvent| {
+            let Some(Ok(all)) = summaries.deref() else { return };
+            let chosen = all.iter().filter(|summary| selected.contains(&summary.key)).cloned().collect::<Vec<_>>();
+            on_requesting_table.emit(());
+            let on_receiving_response = on_receiving_response.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                on_receiving_response.emit(load_combined(chosen).await);
+            });
+        })
+    };
+
+    let content = match summaries.as_ref() {
+        None => html! {},
+        Some(Err(error)) => html! { <div class="alert alert-danger mt-2" role="alert">{error}</div> },
+        Some(Ok(all)) if all.is_empty() => html! {
+            <p class="text-body-secondary mt-2">{"No search is cached in this browser yet."}</p>
+        },
+        Some(Ok(all)) => {
+            let rows = all.iter().map(|summary| {
+                let key = summary.key.clone();
+                let checked = selected.contains(&key);
+                let onchange = {
+                    let selected = selected.clone();
+                    Callback::from(move |_: Event| {
+                        let mut keys = selected.deref().clone();
+                        match checked {
+                            true => keys.retain(|selected_key| *selected_key != key),
+                            false => keys.push(key.clone())
+                        }
+                        selected.set(keys);
+                    })
+                };
+                html! {
+                    <tr>
+                        <td><input class="form-check-input" type="checkbox" {checked} {onchange}/></td>
+                        <td style="word-wrap: break-word">{summary.parameters.input_id_list.join(" ")}</td>
+                        <td>{summary.parameters.depth}</td>
+                        <td>{format!("{:?}", summary.parameters.search_for)}</td>
+                        <td>{summary.article_count}</td>
+                    </tr>
+                }
+            }).collect::<Html>();
+
+            html! {
+                <div class="mt-2">
+                    <table class="table table-sm">
+                        <thead>
+                            <tr><th></th><th>{"Seeds"}</th><th>{"Depth"}</th><th>{"Direction"}</th><th>{"Articles"}</th></tr>
+                        </thead>
+                        <tbody>{rows}</tbody>
+                    </table>
+                    <button class="btn btn-outline-primary" onclick={oncombine} disabled={selected.len() < 2}>
+                        {format!("Combine {} searches", selected.len())}
+                    </button>
+                </div>
+            }
+        }
+    };
+
+    html! {
+        <div class="container-md mb-4">
+            <button class="btn btn-outline-secondary btn-sm" aria-expanded={summaries.is_some().to_string()} onclick={ontoggle}>
+                <i class={classes!("bi", "me-2", if summaries.is_some() { "bi-chevron-down" } else { "bi-chevron-right" })}></i>
+                {"Combine cached searches"}
+            </button>
+            {content}
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{CitationEdge, EdgeKind};
+
+    fn article(doi: &str, score: i32) -> Article {
+        Article { doi: Some(doi.to_string()), score: Some(score), ..Default::default() }
+    }
+
+    fn edge(source: &str, target: &str) -> CitationEdge {
+        CitationEdge { source: source.to_string(), target: target.to_string(), kind: EdgeKind::Reference }
+    }
+
+    #[test]
+    fn combine_merges_articles_sharing_an_identifier() {
+        let a = SnowballResults { articles: vec![article("10.1/x", 3), article("10.1/y", 5)], ..Default::default() };
+        let b = SnowballResults { articles: vec![article("10.1/X", 4)], ..Default::default() };
+        let combined = combine(vec![(source_label(0), a), (source_label(1), b)]);
+
+        assert_eq!(combined.articles.len(), 2);
+        let merged = &combined.articles[0];
+        assert_eq!(merged.score, Some(7));
+        assert_eq!(merged.max_score, Some(4));
+        assert_eq!(merged.sources, Some(vec!["S1".to_string(), "S2".to_string()]));
+        assert_eq!(combined.articles[1].sources, Some(vec!["S1".to_string()]));
+    }
+
+    #[test]
+    fn combine_adds_the_best_score_of_each_search() {
+        let a = SnowballResults { articles: vec![article("10.1/x", 3), article("10.1/X", 6)], ..Default::default() };
+        let b = SnowballResults { articles: vec![article("10.1/x", 4)], ..Default::default() };
+        let combined = combine(vec![(source_label(0), a), (source_label(1), b)]);
+
+        assert_eq!(combined.articles.len(), 1);
+        assert_eq!(combined.articles[0].score, Some(10));
+        assert_eq!(combined.articles[0].max_score, Some(6));
+    }
+
+    #[test]
+    fn combine_deduplicates_edges() {
+        let a = SnowballResults { edges: vec![edge("a", "b"), edge("b", "c")], ..Default::default() };
+        let b = SnowballResults { edges: vec![edge("a", "b"), edge("c", "d")], ..Default::default() };
+        let combined = combine(vec![(source_label(0), a), (source_label(1), b)]);
+
+        assert_eq!(combined.edges, vec![edge("a", "b"), edge("b", "c"), edge("c", "d")]);
+    }
+}
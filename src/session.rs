@@ -0,0 +1,171 @@
+//! Lossless archive of a result set and of the state of its table, upgraded from older versions by `migrate`.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::api::{CitationEdge, SeedResolution, SnowballParameters};
+use crate::common::Error;
+use crate::table::{article, Article, SearchResults, TableState};
+
+/// Version 0 is the bare article array of the JSON export, which predates sessions
+pub const SESSION_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Session {
+    pub version: u32,
+    #[serde(default)]
+    pub saved_at: String,
+    #[serde(default)]
+    pub parameters: Option<SnowballParameters>,
+    pub articles: Vec<Article>,
+    #[serde(default)]
+    pub seeds: Vec<SeedResolution>,
+    #[serde(default)]
+    pub edges: Vec<CitationEdge>,
+    #[serde(default)]
+    pub sources: Vec<String>,
+    /// The selected, annotated and screened articles are identified by their position in `articles`
+    #[serde(default)]
+    pub table: TableState,
+}
+
+impl Session {
+    pub fn new(results: &SearchResults, mut table: TableState) -> Self {
+        let articles = results.articles.borrow().clone();
+        let positions = articles
+            .iter()
+            .enumerate()
+            .map(|(position, article)| (article.id, position))
+            .collect::<HashMap<_, _>>();
+        table.selected = articles
+            .iter()
+            .enumerate()
+            .filter(|(_, article)| table.selected.contains(&article.id))
+            .map(|(position, _)| position)
+            .collect();
+        table.notes = by_position(table.notes, &positions);
+        table.decisions = by_position(table.decisions, &positions);
+
+        Session {
+            version: SESSION_VERSION,
+            saved_at: chrono::Local::now().to_rfc3339(),
+            parameters: results.parameters.clone(),
+            articles,
+            seeds: results.seeds.to_vec(),
+            edges: results.edges.to_vec(),
+            sources: results.sources.to_vec(),
+            table,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<Vec<u8>, Error> {
+        Ok(serde_json::to_vec_pretty(self)?)
+    }
+
+    pub fn from_json(bytes: &[u8]) -> Result<Self, Error> {
+        let value = serde_json::from_slice::<Value>(bytes).map_err(|error| Error::Import(error.to_string()))?;
+        let version = match &value {
+            Value::Array(_) => 0,
+            value => value
+                .get("version")
+                .and_then(Value::as_u64)
+                .ok_or_else(|| Error::Import("the session has no version".to_string()))? as u32
+        };
+        serde_json::from_value(migrate(value, version)?).map_err(|error| Error::Import(error.to_string()))
+    }
+
+    pub fn into_results(self, filename: &str) -> SearchResults {
+        let Session { parameters, mut articles, seeds, edges, sources, table, .. } = self;
+        article::assign_ids(&mut articles);
+        // Sessions migrated from a bare export lack the derived metrics
+        if articles.iter().all(|article| article.percentile_rank.is_none()) {
+            let seed_count = match seeds.is_empty() {
+                true => parameters.as_ref().map(|parameters| parameters.input_id_list.len()).unwrap_or_default(),
+                false => seeds.iter().filter(|seed| seed.resolved).count()
+            };
+            article::compute_metrics(&mut articles, seed_count);
+        }
+
+        SearchResults {
+            articles: Rc::new(RefCell::new(articles)),
+            seeds: Rc::new(seeds),
+            edges: Rc::new(edges),
+            cached_at_ms: None,
+            sources: Rc::new(sources),
+            imported_from: Some(filename.to_string()),
+            parameters,
+            restored: Some(Rc::new(table))
+        }
+    }
+}
+
+fn by_position<T>(values: BTreeMap<usize, T>, positions: &HashMap<usize, usize>) -> BTreeMap<usize, T> {
+    values
+        .into_iter()
+        .filter_map(|(id, value)| Some((*positions.get(&id)?, value)))
+        .collect()
+}
+
+fn upgrade_from_0(value: Value) -> Value {
+    serde_json::json!({ "version": 1, "articles": value })
+}
+
+fn migrate(value: Value, version: u32) -> Result<Value, Error> {
+    match version {
+        SESSION_VERSION => Ok(value),
+        0 => migrate(upgrade_from_0(value), 1),
+        _ => Err(Error::UnsupportedSessionVersion(version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::SnowballResults;
+    use crate::table::{Decision, SortState};
+
+    fn results() -> SearchResults {
+        let articles = ["First", "Second", "Third"]
+            .into_iter()
+            .enumerate()
+            .map(|(rank, title)| Article { title: Some(title.to_string()), score: Some(10 - rank as i32), ..Default::default() })
+            .collect();
+        SearchResults::new(SnowballResults { articles, ..Default::default() }, 1, None)
+    }
+
+    #[test]
+    fn table_state_survives_a_round_trip() {
+        let results = results();
+        // Reversing the table moves the article of id 2 to position 0
+        results.articles.borrow_mut().reverse();
+        let table = TableState {
+            selected: vec![2],
+            sort: Some(SortState { column: "score".to_string(), descending: false }),
+            notes: BTreeMap::from([(2, "Landmark trial".to_string())]),
+            decisions: BTreeMap::from([(0, Decision::Exclude)]),
+            ..Default::default()
+        };
+
+        let bytes = Session::new(&results, table).to_json().unwrap();
+        let restored = Session::from_json(&bytes).unwrap().into_results("session.json");
+        let state = restored.restored.unwrap();
+
+        assert_eq!(restored.articles.borrow()[0].title.as_deref(), Some("Third"));
+        assert_eq!(state.selected, vec![0]);
+        assert_eq!(state.sort, Some(SortState { column: "score".to_string(), descending: false }));
+        assert_eq!(state.notes, BTreeMap::from([(0, "Landmark trial".to_string())]));
+        assert_eq!(state.decisions, BTreeMap::from([(2, Decision::Exclude)]));
+    }
+
+    #[test]
+    fn bare_export_is_migrated_without_table_state() {
+        let session = Session::from_json(br#"[{"title": "Only"}]"#).unwrap();
+        assert_eq!(session.version, SESSION_VERSION);
+        assert_eq!(session.articles.len(), 1);
+        assert_eq!(session.table, TableState::default());
+    }
+}
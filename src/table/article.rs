@@ -1,13 +1,44 @@
+use std::cmp::Ordering;
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
-use crate::table::Filters;
+/// Derived floating point value, totally ordered so that its column can be sorted like the others
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Metric(pub f64);
+
+impl PartialEq for Metric {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Metric {}
+
+impl PartialOrd for Metric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Metric {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
 
-fn unwrap_int(year: &Option<i32>) -> Option<String> {
-    Some(format!("{}", (*year)?))
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2}", self.0)
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct Article {
+    /// Identifies the article within the current result set, whatever the ordering of the table
+    #[serde(skip)]
+    pub id: usize,
     pub first_author: Option<String>,
     pub year_published: Option<i32>,
     pub journal: Option<String>,
@@ -15,31 +46,85 @@ pub struct Article {
     pub summary: Option<String>,
     pub doi: Option<String>,
     pub citations: Option<i32>,
-    pub score: Option<i32>
+    pub score: Option<i32>,
+    pub pmid: Option<String>,
+    pub pmcid: Option<String>,
+    pub lens_id: Option<String>,
+    pub authors: Option<Vec<String>>,
+    pub publication_type: Option<String>,
+    pub volume: Option<String>,
+    pub issue: Option<String>,
+    pub pages: Option<String>,
+    pub language: Option<String>,
+    pub keywords: Option<Vec<String>>,
+    pub score_per_citation: Option<Metric>,
+    pub score_per_seed: Option<Metric>,
+    pub percentile_rank: Option<i32>,
+    pub sources: Option<Vec<String>>,
+    /// Best score among the combined searches, `score` being their sum
+    pub max_score: Option<i32>
 }
 
 impl Article {
-    pub fn matches_global(&self, pattern: &str) -> bool {
-        self.doi.as_ref().map_or(false, |x| x.contains(pattern)) |
-        self.title.as_ref().map_or(false, |x| x.contains(pattern)) |
-        self.journal.as_ref().map_or(false, |x| x.contains(pattern)) |
-        self.summary.as_ref().map_or(false, |x| x.contains(pattern)) |
-        self.first_author.as_ref().map_or(false, |x| x.contains(pattern)) |
-        self.year_published.map_or(false, |x| x.to_string().contains(pattern)) |
-        self.score.map_or(false, |x| x.to_string().contains(pattern)) |
-        self.citations.map_or(false, |x| x.to_string().contains(pattern))       
+    pub fn doi_link(&self) -> Option<String> {
+        Some(format!("https://doi.org/{}", self.doi.as_ref()?))
+    }
+
+    pub fn pubmed_link(&self) -> Option<String> {
+        Some(format!("https://pubmed.ncbi.nlm.nih.gov/{}/", self.pmid.as_ref()?))
+    }
+
+    pub fn pmc_link(&self) -> Option<String> {
+        Some(format!("https://www.ncbi.nlm.nih.gov/pmc/articles/{}/", self.pmcid.as_ref()?))
     }
 
+    pub fn lens_link(&self) -> Option<String> {
+        Some(format!("https://www.lens.org/lens/scholar/article/{}/main", self.lens_id.as_ref()?))
+    }
+
+    pub fn authors_joined(&self) -> Option<String> {
+        Some(self.authors.as_ref()?.join(", "))
+    }
+
+    pub fn keywords_joined(&self) -> Option<String> {
+        Some(self.keywords.as_ref()?.join(", "))
+    }
+
+    pub fn sources_joined(&self) -> Option<String> {
+        Some(self.sources.as_ref()?.join(", "))
+    }
+
+    pub fn identifiers(&self) -> Vec<String> {
+        [("doi", &self.doi), ("pmid", &self.pmid), ("pmcid", &self.pmcid), ("lens", &self.lens_id)]
+            .into_iter()
+            .filter_map(|(kind, id)| Some(format!("{kind}:{}", id.as_deref()?.trim().to_lowercase())))
+            .filter(|id| !id.ends_with(':'))
+            .collect()
+    }
+}
+
+pub fn assign_ids(articles: &mut [Article]) {
+    for (id, article) in articles.iter_mut().enumerate() {
+        article.id = id;
+    }
+}
 
-    pub fn matches(&self, filters: &Filters) -> bool {
-        self.doi.as_ref().map_or(false, |x| x.contains(&filters.doi)) &
-        self.title.as_ref().map_or(false, |x| x.contains(&filters.title)) &
-        self.journal.as_ref().map_or(false, |x| x.contains(&filters.journal)) &
-        self.summary.as_ref().map_or(false, |x| x.contains(&filters.summary)) &
-        self.first_author.as_ref().map_or(false, |x| x.contains(&filters.first_author)) &
-        self.year_published.map_or(false, |x| x.to_string().contains(&filters.year_published)) &
-        self.score.map_or(false, |x| x.to_string().contains(&filters.score)) &
-        self.citations.map_or(false, |x| x.to_string().contains(&filters.citations))
+pub fn compute_metrics(articles: &mut [Article], seed_count: usize) {
+    for article in articles.iter_mut() {
+        let Some(score) = article.score else { continue };
+        article.score_per_seed = (seed_count > 0).then(|| Metric(score as f64 / seed_count as f64));
     }
+    update_relative_metrics(articles);
 }
 
+pub fn update_relative_metrics(articles: &mut [Article]) {
+    let mut scores = articles.iter().filter_map(|article| article.score).collect::<Vec<_>>();
+    scores.sort_unstable();
+
+    for article in articles.iter_mut() {
+        let Some(score) = article.score else { continue };
+        article.score_per_citation = article.citations.map(|citations| Metric(score as f64 / citations.max(1) as f64));
+        let at_most = scores.partition_point(|other| *other <= score);
+        article.percentile_rank = Some((100.0 * at_most as f64 / scores.len() as f64).round() as i32);
+    }
+}
@@ -0,0 +1,94 @@
+use yew::prelude::*;
+
+use crate::table::Article;
+use crate::table::screening::{Decision, DecisionButtons, NoteInput};
+
+#[derive(Clone, PartialEq, Properties)]
+struct DetailFieldProps {
+    label: AttrValue,
+    value: Option<String>,
+    #[prop_or_default]
+    link: Option<String>
+}
+
+#[function_component(DetailField)]
+fn detail_field(props: &DetailFieldProps) -> Html {
+    let Some(value) = props.value.clone() else {
+        return html! {};
+    };
+
+    html! {
+        <>
+            <dt class="col-sm-4">{props.label.clone()}</dt>
+            <dd class="col-sm-8" style="word-wrap: break-word">
+                if let Some(link) = props.link.clone() {
+                    <a href={link}>{value}</a>
+                } else {
+                    {value}
+                }
+            </dd>
+        </>
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct ArticleDetailProps {
+    pub article: Article,
+    pub on_close: Callback<()>,
+    pub decision: Option<Decision>,
+    pub note: Option<String>,
+    pub on_decide: Callback<(usize, Option<Decision>)>,
+    pub on_note: Callback<(usize, String)>
+}
+
+#[function_component(ArticleDetail)]
+pub fn article_detail(props: &ArticleDetailProps) -> Html {
+    let onclick = {
+        let on_close = props.on_close.clone();
+        Callback::from(move |_: MouseEvent| {
+            on_close.emit(());
+        })
+    };
+
+    let article = &props.article;
+    let number = |value: Option<i32>| value.map(|x| x.to_string());
+
+    html! {
+        <div class="offcanvas offcanvas-end show article-detail" tabindex="-1" aria-labelledby="articleDetailLabel">
+            <div class="offcanvas-header">
+                <h5 class="offcanvas-title" id="articleDetailLabel">{article.title.clone().unwrap_or_default()}</h5>
+                <button type="button" class="btn-close" aria-label="Close" {onclick}></button>
+            </div>
+            <div class="offcanvas-body">
+                <div class="d-flex align-items-start gap-2 mb-3">
+                    <DecisionButtons id={article.id} decision={props.decision} on_decide={props.on_decide.clone()}/>
+                    <NoteInput id={article.id} note={props.note.clone()} on_note={props.on_note.clone()} multiline=true/>
+                </div>
+                <dl class="row">
+                    <DetailField label="DOI" value={article.doi.clone()} link={article.doi_link()}/>
+                    <DetailField label="PMID" value={article.pmid.clone()} link={article.pubmed_link()}/>
+                    <DetailField label="PMCID" value={article.pmcid.clone()} link={article.pmc_link()}/>
+                    <DetailField label="Lens ID" value={article.lens_id.clone()} link={article.lens_link()}/>
+                    <DetailField label="Authors" value={article.authors_joined().or(article.first_author.clone())}/>
+                    <DetailField label="Journal" value={article.journal.clone()}/>
+                    <DetailField label="Volume" value={article.volume.clone()}/>
+                    <DetailField label="Issue" value={article.issue.clone()}/>
+                    <DetailField label="Pages" value={article.pages.clone()}/>
+                    <DetailField label="Year published" value={number(article.year_published)}/>
+                    <DetailField label="Publication type" value={article.publication_type.clone()}/>
+                    <DetailField label="Language" value={article.language.clone()}/>
+                    <DetailField label="Keywords" value={article.keywords_joined()}/>
+                    <DetailField label="Citations" value={number(article.citations)}/>
+                    <DetailField label="Score" value={number(article.score)}/>
+                    <DetailField label="Score per citation" value={article.score_per_citation.map(|x| x.to_string())}/>
+                    <DetailField label="Score per seed" value={article.score_per_seed.map(|x| x.to_string())}/>
+                    <DetailField label="Percentile rank" value={number(article.percentile_rank)}/>
+                    <DetailField label="Sources" value={article.sources_joined()}/>
+                    <DetailField label="Max score" value={number(article.max_score)}/>
+                </dl>
+                <h6>{"Summary"}</h6>
+                <p>{article.summary.clone().unwrap_or_default()}</p>
+            </div>
+        </div>
+    }
+}
@@ -5,12 +5,55 @@ use yew::prelude::*;
 use crate::common;
 use crate::table::Article;
 
-#[allow(dead_code)]
+/// Separates the entries of list columns, names such as "Smith, John" containing commas
+pub const LIST_SEPARATOR: &str = "; ";
+
+fn export_list(list: &Option<Vec<String>>) -> Option<String> {
+    Some(list.as_ref()?.join(LIST_SEPARATOR))
+}
+
+pub type ExportColumn = (&'static str, fn(&Article) -> Option<String>);
+
+pub const EXPORT_COLUMNS: [ExportColumn; 23] = [
+    ("doi", |a| a.doi.clone()),
+    ("Title", |a| a.title.clone()),
+    ("Journal", |a| a.journal.clone()),
+    ("Year published", |a| a.year_published.map(|x| x.to_string())),
+    ("Summary", |a| a.summary.clone()),
+    ("Citations", |a| a.citations.map(|x| x.to_string())),
+    ("Score", |a| a.score.map(|x| x.to_string())),
+    ("Score per citation", |a| a.score_per_citation.map(|x| x.to_string())),
+    ("Score per seed", |a| a.score_per_seed.map(|x| x.to_string())),
+    ("Percentile rank", |a| a.percentile_rank.map(|x| x.to_string())),
+    ("Sources", |a| export_list(&a.sources)),
+    ("Max score", |a| a.max_score.map(|x| x.to_string())),
+    ("First author", |a| a.first_author.clone()),
+    ("Authors", |a| export_list(&a.authors)),
+    ("PMID", |a| a.pmid.clone()),
+    ("PMCID", |a| a.pmcid.clone()),
+    ("Lens ID", |a| a.lens_id.clone()),
+    ("Publication type", |a| a.publication_type.clone()),
+    ("Volume", |a| a.volume.clone()),
+    ("Issue", |a| a.issue.clone()),
+    ("Pages", |a| a.pages.clone()),
+    ("Language", |a| a.language.clone()),
+    ("Keywords", |a| export_list(&a.keywords)),
+];
+
+fn export_header() -> impl Iterator<Item = &'static str> {
+    EXPORT_COLUMNS.iter().map(|(header, _)| *header)
+}
+
+fn export_record(article: &Article) -> impl Iterator<Item = String> + '_ {
+    EXPORT_COLUMNS.iter().map(|(_, value)| value(article).unwrap_or_default())
+}
+
 pub fn to_csv(articles: &[Article]) -> Result<Vec<u8>, common::Error> {
     let mut wtr = csv::Writer::from_writer(Vec::new());
 
+    wtr.write_record(export_header())?;
     for article in articles.iter() {
-        wtr.serialize(article)?;
+        wtr.write_record(export_record(article))?;
     }
 
     wtr.flush()?;
@@ -27,42 +70,34 @@ pub fn to_excel(articles: &[Article]) -> Result<Vec<u8>, common::Error> {
     let mut workbook = Workbook::new();
     let worksheet = workbook.add_worksheet();
 
-    worksheet.write_string(0, 0, "doi")?;
-    worksheet.write_string(0, 1, "Title")?;
-    worksheet.write_string(0, 2, "Journal")?;
-    worksheet.write_string(0, 3, "Year published")?;
-    worksheet.write_string(0, 4, "Summary")?;
-    worksheet.write_string(0, 5, "Citations")?;
-    worksheet.write_string(0, 6, "Score")?;
-
     let text_format = rust_xlsxwriter::Format::new()
         .set_text_wrap()
         .set_align(rust_xlsxwriter::FormatAlign::Top);
 
-    for col in 0..=6 {
+    let last_col: u16 = (EXPORT_COLUMNS.len() - 1).try_into()?;
+    for (col, header) in export_header().enumerate() {
+        let col: u16 = col.try_into()?;
+        worksheet.write_string(0, col, header)?;
         worksheet.set_column_format(col, &text_format)?;
     }
-    
 
     for (i, article) in articles.iter().enumerate() {
         let i : u32 = i.try_into()?;
 
-        worksheet.write_string(i + 1, 0, article.doi.clone().unwrap_or_default())?;
-        worksheet.write_string(i + 1, 1, article.title.clone().unwrap_or_default())?;
-        worksheet.write_string(i + 1, 2, article.journal.clone().unwrap_or_default())?;
-        worksheet.write_string(i + 1, 3, article.year_published.unwrap_or_default().to_string())?;
-        worksheet.write_string(i + 1, 4, article.summary.clone().unwrap_or_default())?;
-        worksheet.write_string(i + 1, 5, article.citations.unwrap_or_default().to_string())?;
-        worksheet.write_string(i + 1, 6, article.score.unwrap_or_default().to_string())?;
+        for (col, value) in export_record(article).enumerate() {
+            worksheet.write_string(i + 1, col.try_into()?, value)?;
+        }
 
         worksheet.set_row_height(i + 1, 150)?;
     }
 
     worksheet.autofit();
-    worksheet.set_column_width(1, 52)?;
-    worksheet.set_column_width(2, 52)?;
-    worksheet.set_column_width(4, 52)?;
-    worksheet.autofilter(0, 0, articles.len().try_into()?, 6)?;
+    for (col, header) in export_header().enumerate() {
+        if matches!(header, "Title" | "Journal" | "Summary" | "Authors") {
+            worksheet.set_column_width(col.try_into()?, 52)?;
+        }
+    }
+    worksheet.autofilter(0, 0, articles.len().try_into()?, last_col)?;
     
 
     let buf = workbook.save_to_buffer()?;
@@ -88,16 +123,49 @@ pub fn download_bytes_as_file(bytes: &[u8], filename: &str) -> Result<(), common
 }
 
 
+pub fn to_json(articles: &[Article]) -> Result<Vec<u8>, common::Error> {
+    Ok(serde_json::to_vec_pretty(articles)?)
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ExportFormat {
+    Excel,
+    Csv,
+    Json
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Excel => "xlsx",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json"
+        }
+    }
+
+    pub fn export(&self, articles: &[Article]) -> Result<Vec<u8>, common::Error> {
+        match self {
+            ExportFormat::Excel => to_excel(articles),
+            ExportFormat::Csv => to_csv(articles),
+            ExportFormat::Json => to_json(articles)
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Properties)]
 pub struct ButtonProps {
-    pub onclick: Callback<MouseEvent>
+    pub on_download: Callback<ExportFormat>
 }
 
 #[function_component(DownloadButton)]
 pub fn download_button(props: &ButtonProps) -> Html {
+    let download = |format: ExportFormat| props.on_download.reform(move |_: MouseEvent| format);
+
     html! {
-        <div>
-            <button class="btn btn-outline-secondary btn-lg mb-10" onclick={props.onclick.clone()}><i class="bi bi-download me-2"></i>{"Download articles"}</button>
+        <div class="btn-group mb-10" role="group" aria-label="Download articles">
+            <button class="btn btn-outline-secondary btn-lg" onclick={download(ExportFormat::Excel)}><i class="bi bi-download me-2"></i>{"Download articles"}</button>
+            <button class="btn btn-outline-secondary btn-lg" onclick={download(ExportFormat::Csv)}>{"CSV"}</button>
+            <button class="btn btn-outline-secondary btn-lg" onclick={download(ExportFormat::Json)}>{"JSON"}</button>
         </div>
     }
 }
\ No newline at end of file
@@ -0,0 +1,347 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::ops::Deref;
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::table::Article;
+
+/// Minimal Dice coefficient between the title trigrams of two records of the same article
+pub const TITLE_SIMILARITY_THRESHOLD: f64 = 0.85;
+
+fn normalize_title(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn normalize_doi(doi: &str) -> String {
+    let doi = doi.trim().to_lowercase();
+    doi.strip_prefix("https://doi.org/").map(str::to_string).unwrap_or(doi)
+}
+
+/// Last name of the first author, which is the part formatted consistently across sources
+fn normalize_author(author: &str) -> Option<String> {
+    author
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| word.len() > 1)
+        .max_by_key(|word| word.len())
+        .map(str::to_lowercase)
+}
+
+fn trigrams(title: &str) -> HashSet<(char, char, char)> {
+    let chars = format!(" {title} ").chars().collect::<Vec<_>>();
+    chars.windows(3).map(|w| (w[0], w[1], w[2])).collect()
+}
+
+pub fn similarity(a: &HashSet<(char, char, char)>, b: &HashSet<(char, char, char)>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    2.0 * a.intersection(b).count() as f64 / (a.len() + b.len()) as f64
+}
+
+struct Fingerprint {
+    doi: Option<String>,
+    year: Option<i32>,
+    author: Option<String>,
+    title: HashSet<(char, char, char)>
+}
+
+impl From<&Article> for Fingerprint {
+    fn from(article: &Article) -> Self {
+        let title = article.title.as_deref().map(normalize_title).unwrap_or_default();
+        Fingerprint {
+            doi: article.doi.as_deref().map(normalize_doi).filter(|doi| !doi.is_empty()),
+            year: article.year_published,
+            author: article.first_author.as_deref().and_then(normalize_author),
+            title: trigrams(&title)
+        }
+    }
+}
+
+impl Fingerprint {
+    /// Similar titles with compatible years and first authors (a preprint may precede its publication by a year)
+    fn is_similar_to(&self, other: &Fingerprint) -> bool {
+        compatible(&self.year, &other.year, |a, b| (a - b).abs() <= 1) &&
+        compatible(&self.author, &other.author, |a, b| a == b) &&
+        // Cheap bound before computing the similarity: the Dice coefficient cannot reach the threshold otherwise
+        self.title.len().min(other.title.len()) as f64 >= TITLE_SIMILARITY_THRESHOLD / (2.0 - TITLE_SIMILARITY_THRESHOLD) * self.title.len().max(other.title.len()) as f64 &&
+        similarity(&self.title, &other.title) >= TITLE_SIMILARITY_THRESHOLD
+    }
+}
+
+fn compatible<T>(a: &Option<T>, b: &Option<T>, f: fn(&T, &T) -> bool) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => f(a, b),
+        _ => true
+    }
+}
+
+fn find(parents: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    parents[i] = root;
+    root
+}
+
+fn union(parents: &mut [usize], i: usize, j: usize) {
+    let (root_i, root_j) = (find(parents, i), find(parents, j));
+    if root_i != root_j {
+        parents[root_j] = root_i;
+    }
+}
+
+pub fn find_duplicates(articles: &[Article]) -> Vec<Vec<usize>> {
+    let fingerprints = articles.iter().map(Fingerprint::from).collect::<Vec<_>>();
+    let mut parents = (0..fingerprints.len()).collect::<Vec<_>>();
+
+    // Records sharing a DOI are the same article whatever their years
+    let mut by_doi = HashMap::<&str, usize>::new();
+    for (i, fingerprint) in fingerprints.iter().enumerate() {
+        if let Some(doi) = &fingerprint.doi {
+            let first = *by_doi.entry(doi).or_insert(i);
+            union(&mut parents, first, i);
+        }
+    }
+
+    // Records whose years differ by more than one are never compared, those without a year being compared with all the others
+    let mut by_year = HashMap::<Option<i32>, Vec<usize>>::new();
+    for (i, fingerprint) in fingerprints.iter().enumerate() {
+        by_year.entry(fingerprint.year).or_default().push(i);
+    }
+    let bucket = |year: Option<i32>| by_year.get(&year).map(Vec::as_slice).unwrap_or_default();
+    for (i, fingerprint) in fingerprints.iter().enumerate() {
+        let same_year = bucket(fingerprint.year).iter().filter(|j| **j > i);
+        let candidates = match fingerprint.year {
+            Some(year) => same_year.chain(bucket(Some(year + 1))).chain(bucket(None)).copied().collect::<Vec<_>>(),
+            None => same_year.copied().collect()
+        };
+        for j in candidates {
+            if find(&mut parents, i) != find(&mut parents, j) && fingerprint.is_similar_to(&fingerprints[j]) {
+                union(&mut parents, i, j);
+            }
+        }
+    }
+
+    let mut groups = HashMap::<usize, Vec<usize>>::new();
+    for i in 0..articles.len() {
+        let root = find(&mut parents, i);
+        groups.entry(root).or_default().push(i);
+    }
+    let mut groups = groups
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(|mut group| {
+            group.sort_by_key(|i| std::cmp::Reverse(articles[*i].score.unwrap_or_default()));
+            group.into_iter().map(|i| articles[i].id).collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    groups.sort();
+    groups
+}
+
+macro_rules! fill_missing {
+    ($kept:ident, $other:ident, $($field:ident),*) => {
+        $(if $kept.$field.is_none() { $kept.$field = $other.$field.clone(); })*
+    };
+}
+
+pub fn fill_missing_fields(kept: &mut Article, other: &Article) {
+    fill_missing!(kept, other, first_author, year_published, journal, title, summary, doi, pmid, pmcid, lens_id,
+        authors, publication_type, volume, issue, pages, language, keywords);
+    kept.citations = kept.citations.max(other.citations);
+}
+
+pub fn merge_group(articles: &mut Vec<Article>, ids: &[usize]) -> Vec<usize> {
+    let members = articles.iter().filter(|article| ids.contains(&article.id)).cloned().collect::<Vec<_>>();
+    let Some(mut kept) = members.iter().max_by_key(|article| article.score.unwrap_or_default()).cloned() else { return Vec::new() };
+
+    for other in members.iter() {
+        fill_missing_fields(&mut kept, other);
+    }
+
+    let removed = ids.iter().copied().filter(|id| *id != kept.id).collect::<Vec<_>>();
+    articles.retain(|article| !removed.contains(&article.id));
+    if let Some(article) = articles.iter_mut().find(|article| article.id == kept.id) {
+        *article = kept;
+    }
+    removed
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct DuplicatesPanelProps {
+    pub articles: Rc<RefCell<Vec<Article>>>,
+    pub on_merge: Callback<Vec<usize>>
+}
+
+#[function_component(DuplicatesPanel)]
+pub fn duplicates_panel(props: &DuplicatesPanelProps) -> Html {
+    let groups = use_state(|| None::<Vec<Vec<usize>>>);
+
+    {
+        let groups = groups.clone();
+        use_effect_with(Rc::as_ptr(&props.articles) as usize, move |_| {
+            groups.set(None);
+        });
+    }
+
+    let onfind = {
+        let groups = groups.clone();
+        let articles = props.articles.clone();
+        Callback::from(move |_: MouseEvent| {
+            match *groups {
+                Some(_) => groups.set(None),
+                None => groups.set(Some(find_duplicates(&articles.borrow())))
+            }
+        })
+    };
+
+    let merge = |selected: Option<usize>| {
+        let groups = groups.clone();
+        let articles = props.articles.clone();
+        let on_merge = props.on_merge.clone();
+        Callback::from(move |_: MouseEvent| {
+            let Some(current) = groups.deref().clone() else { return };
+            let (merged, remaining): (Vec<_>, Vec<_>) = current
+                .into_iter()
+                .enumerate()
+                .partition(|(i, _)| selected.is_none_or(|selected| selected == *i));
+            let removed = merged
+                .into_iter()
+                .flat_map(|(_, group)| merge_group(&mut articles.borrow_mut(), &group))
+                .collect::<Vec<_>>();
+            groups.set(Some(remaining.into_iter().map(|(_, group)| group).collect()));
+            on_merge.emit(removed);
+        })
+    };
+    let dismiss = |selected: usize| {
+        let groups = groups.clone();
+        Callback::from(move |_: MouseEvent| {
+            let Some(mut current) = groups.deref().clone() else { return };
+            current.remove(selected);
+            groups.set(Some(current));
+        })
+    };
+
+    let content = groups.as_ref().map(|groups| {
+        let articles = props.articles.borrow();
+        let by_id = articles.iter().map(|article| (article.id, article)).collect::<HashMap<_, _>>();
+        let group_cards = groups.iter().enumerate().map(|(i, group)| {
+            let rows = group.iter().filter_map(|id| by_id.get(id)).enumerate().map(|(rank, article)| html! {
+                <tr class={classes!((rank == 0).then_some("table-success"))}>
+                    <td>{article.title.clone().unwrap_or_default()}</td>
+                    <td>{article.first_author.clone().unwrap_or_default()}</td>
+                    <td>{article.year_published.map(|x| x.to_string()).unwrap_or_default()}</td>
+                    <td>{article.doi.clone().unwrap_or_default()}</td>
+                    <td>{article.score.unwrap_or_default()}</td>
+                </tr>
+            }).collect::<Html>();
+            html! {
+                <div class="card mb-2">
+                    <div class="card-body p-2">
+                        <table class="table table-sm mb-2">
+                            <thead><tr><th>{"Title"}</th><th>{"First author"}</th><th>{"Year"}</th><th>{"DOI"}</th><th>{"Score"}</th></tr></thead>
+                            <tbody>{rows}</tbody>
+                        </table>
+                        <button class="btn btn-outline-primary btn-sm me-2" onclick={merge(Some(i))}>{"Merge, keeping the highlighted record"}</button>
+                        <button class="btn btn-outline-secondary btn-sm" onclick={dismiss(i)}>{"Not duplicates"}</button>
+                    </div>
+                </div>
+            }
+        }).collect::<Html>();
+
+        html! {
+            <div class="mt-2">
+                if groups.is_empty() {
+                    <p class="text-body-secondary">{"No suspected duplicates."}</p>
+                } else {
+                    <div class="d-flex align-items-center gap-2 mb-2">
+                        <span>{format!("{} suspected duplicate groups", groups.len())}</span>
+                        <button class="btn btn-outline-primary btn-sm" onclick={merge(None)}>{"Merge all"}</button>
+                    </div>
+                    {group_cards}
+                }
+            </div>
+        }
+    });
+
+    html! {
+        <div class="mb-3">
+            <button class="btn btn-outline-secondary btn-sm" aria-expanded={groups.is_some().to_string()} onclick={onfind}>
+                <i class={classes!("bi", "me-2", if groups.is_some() { "bi-chevron-down" } else { "bi-chevron-right" })}></i>
+                {"Find duplicates"}
+            </button>
+            {content}
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(id: usize, title: &str, year: i32, doi: Option<&str>, score: i32) -> Article {
+        Article {
+            id,
+            title: Some(title.to_string()),
+            year_published: Some(year),
+            first_author: Some("Smith J".to_string()),
+            doi: doi.map(str::to_string),
+            score: Some(score),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn finds_preprints_and_records_sharing_a_doi() {
+        let articles = vec![
+            article(0, "Deep learning for chest radiographs: a cohort study", 2021, Some("10.1/a"), 10),
+            article(1, "Deep Learning for Chest Radiographs - A Cohort Study", 2020, None, 4),
+            article(2, "An unrelated trial", 2021, Some("https://doi.org/10.1/A"), 2),
+            article(3, "Something else entirely", 2021, Some("10.1/b"), 8)
+        ];
+        assert_eq!(find_duplicates(&articles), vec![vec![0, 1, 2]]);
+    }
+
+    #[test]
+    fn records_sharing_a_doi_are_duplicates_whatever_their_years() {
+        let articles = vec![
+            article(0, "Deep learning for chest radiographs", 2010, Some("10.1/a"), 1),
+            article(1, "Chest radiographs, revisited", 2021, Some("10.1/A"), 5)
+        ];
+        assert_eq!(find_duplicates(&articles), vec![vec![1, 0]]);
+    }
+
+    #[test]
+    fn distant_years_are_not_duplicates() {
+        let articles = vec![
+            article(0, "Deep learning for chest radiographs", 2010, None, 1),
+            article(1, "Deep learning for chest radiographs", 2021, None, 1)
+        ];
+        assert!(find_duplicates(&articles).is_empty());
+    }
+
+    #[test]
+    fn merge_keeps_the_best_score_and_completes_it() {
+        let mut best = article(1, "A title", 2021, None, 9);
+        best.citations = Some(3);
+        let mut other = article(0, "A title", 2020, Some("10.1/a"), 2);
+        other.citations = Some(5);
+        let mut articles = vec![other, best, article(2, "Kept", 2021, None, 1)];
+
+        let removed = merge_group(&mut articles, &[0, 1]);
+
+        assert_eq!(removed, vec![0]);
+        assert_eq!(articles.iter().map(|article| article.id).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(articles[0].score, Some(9));
+        assert_eq!(articles[0].doi.as_deref(), Some("10.1/a"));
+        assert_eq!(articles[0].citations, Some(5));
+    }
+}
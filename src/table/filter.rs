@@ -1,4 +1,9 @@
-#[derive(Default, PartialEq, Debug)]
+use serde::{Deserialize, Serialize};
+
+use crate::table::Article;
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Filters {
     pub first_author: String,
     pub year_published: String,
@@ -8,4 +13,134 @@ pub struct Filters {
     pub doi: String,
     pub citations: String,
     pub score: String,
+    pub score_per_citation: String,
+    pub score_per_seed: String,
+    pub percentile_rank: String,
+    pub sources: String,
+    pub max_score: String,
+    pub year_range: Option<(i32, i32)>,
+    pub journal_facet: Option<String>,
+    pub first_author_facet: Option<String>,
+    pub min_score: Option<i32>,
+}
+
+pub const FILTER_DEBOUNCE_MS: u32 = 250;
+
+pub fn normalize(value: &str) -> String {
+    value.to_lowercase()
+}
+
+#[derive(Default, PartialEq, Debug)]
+pub struct IndexedArticle {
+    first_author: Option<String>,
+    year_published: Option<String>,
+    title: Option<String>,
+    journal: Option<String>,
+    summary: Option<String>,
+    doi: Option<String>,
+    citations: Option<String>,
+    score: Option<String>,
+    score_per_citation: Option<String>,
+    score_per_seed: Option<String>,
+    percentile_rank: Option<String>,
+    sources: Option<String>,
+    max_score: Option<String>,
+    year: Option<i32>,
+    score_value: Option<i32>,
+    /// Identifiers, full author list and keywords, only searched by the global filter
+    other_fields: String,
+    /// All the fields above separated by newlines, which cannot be typed in a filter input
+    all_fields: String,
+}
+
+impl From<&Article> for IndexedArticle {
+    fn from(article: &Article) -> Self {
+        let text = |field: &Option<String>| field.as_deref().map(normalize);
+        let number = |field: &Option<i32>| field.map(|x| x.to_string());
+
+        let mut indexed = IndexedArticle {
+            first_author: text(&article.first_author),
+            year_published: number(&article.year_published),
+            title: text(&article.title),
+            journal: text(&article.journal),
+            summary: text(&article.summary),
+            doi: text(&article.doi),
+            citations: number(&article.citations),
+            score: number(&article.score),
+            score_per_citation: article.score_per_citation.map(|x| x.to_string()),
+            score_per_seed: article.score_per_seed.map(|x| x.to_string()),
+            percentile_rank: number(&article.percentile_rank),
+            sources: text(&article.sources_joined()),
+            max_score: number(&article.max_score),
+            year: article.year_published,
+            score_value: article.score,
+            other_fields: [article.pmid.clone(), article.pmcid.clone(), article.lens_id.clone(), article.authors_joined(),
+                    article.publication_type.clone(), article.keywords_joined()]
+                .into_iter()
+                .flatten()
+                .map(|field| normalize(&field))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            all_fields: String::new(),
+        };
+
+        indexed.all_fields = [&indexed.doi, &indexed.title, &indexed.journal, &indexed.summary, &indexed.first_author,
+                &indexed.year_published, &indexed.score, &indexed.citations, &Some(indexed.other_fields.clone())]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        indexed
+    }
+}
+
+fn field_matches(field: &Option<String>, pattern: &str) -> bool {
+    pattern.is_empty() || field.as_ref().is_some_and(|x| x.contains(pattern))
+}
+
+fn facet_matches(field: &Option<String>, facet: &Option<String>) -> bool {
+    facet.is_none() || field == facet
+}
+
+impl IndexedArticle {
+    pub fn matches_global(&self, pattern: &str) -> bool {
+        self.all_fields.contains(pattern)
+    }
+
+    pub fn matches(&self, filters: &Filters) -> bool {
+        field_matches(&self.doi, &filters.doi) &
+        field_matches(&self.title, &filters.title) &
+        field_matches(&self.journal, &filters.journal) &
+        field_matches(&self.summary, &filters.summary) &
+        field_matches(&self.first_author, &filters.first_author) &
+        field_matches(&self.year_published, &filters.year_published) &
+        field_matches(&self.score, &filters.score) &
+        field_matches(&self.citations, &filters.citations) &
+        field_matches(&self.score_per_citation, &filters.score_per_citation) &
+        field_matches(&self.score_per_seed, &filters.score_per_seed) &
+        field_matches(&self.percentile_rank, &filters.percentile_rank) &
+        field_matches(&self.sources, &filters.sources) &
+        field_matches(&self.max_score, &filters.max_score) &
+        facet_matches(&self.journal, &filters.journal_facet) &
+        facet_matches(&self.first_author, &filters.first_author_facet) &
+        filters.year_range.is_none_or(|(from, to)| self.year.is_some_and(|year| (from..=to).contains(&year))) &
+        filters.min_score.is_none_or(|min_score| self.score_value.is_some_and(|score| score >= min_score))
+    }
+}
+
+pub fn build_index(articles: &[Article]) -> Vec<IndexedArticle> {
+    articles.iter().map(IndexedArticle::from).collect()
+}
+
+pub fn filter_articles(index: &[IndexedArticle], global_filter: &str, filters: &Filters) -> Vec<usize> {
+    let global_filter = normalize(global_filter);
+    index
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| a.matches_global(&global_filter))
+        .filter(|(_, a)| a.matches(filters))
+        .map(|(i, _)| i)
+        .collect()
 }
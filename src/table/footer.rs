@@ -1,4 +1,4 @@
-use std::ops::Deref;
+use std::ops::{Deref, Range};
 
 use wasm_bindgen::JsCast;
 use yew::prelude::*;
@@ -11,54 +11,95 @@ pub struct TableFooterProps {
     pub table_current_page: UseStateHandle<i32>
 }
 
+pub fn page_count(article_total_number: usize, articles_per_page: i32) -> i32 {
+    if articles_per_page <= 0 {
+        return 0;
+    }
+    let articles_per_page = articles_per_page as usize;
+    article_total_number.div_ceil(articles_per_page) as i32
+}
+
+pub fn shown_articles(current_page: i32, articles_per_page: i32, article_total_number: usize) -> Option<(usize, usize)> {
+    if current_page < 0 || articles_per_page <= 0 {
+        return None;
+    }
+    let first_article = current_page as usize * articles_per_page as usize;
+    if first_article >= article_total_number {
+        return None;
+    }
+    let last_article = (first_article + articles_per_page as usize).min(article_total_number);
+
+    Some((first_article + 1, last_article))
+}
+
+pub fn contiguous_pages(current_page: i32, page_count: i32, radius: i32) -> Range<i32> {
+    let width = 2*radius + 1;
+    let low_bound = (current_page - radius).clamp(0, (page_count - width).max(0));
+    let high_bound = (low_bound + width).min(page_count);
+
+    low_bound..high_bound
+}
+
+fn scroll_to_table() {
+    let element = gloo_utils::document()
+        .get_element_by_id("table")
+        .and_then(|element| element.dyn_into::<HtmlElement>().ok());
+    if let Some(element) = element {
+        element.scroll_into_view();
+    }
+}
+
 #[function_component(TableFooter)]
 pub fn table_footer(props: &TableFooterProps) -> Html {
     let table_current_page = props.table_current_page.deref().to_owned();
     let articles_per_page = props.articles_per_page.deref().to_owned();
-    let first_article = table_current_page * articles_per_page + 1;
-    let last_article = first_article + articles_per_page - 1;
 
-    let total_page_number = (props.article_total_number as i32) / articles_per_page;
+    let total_page_number = page_count(props.article_total_number, articles_per_page);
     let last_page_index = total_page_number - 1;
-    
-    let contiguous_window_radius = 2;
-    let contiguous_low_bound = (table_current_page - contiguous_window_radius).clamp(0, total_page_number);
-    let contiguous_high_bound = (contiguous_low_bound + 2*contiguous_window_radius + 1).clamp(0, total_page_number);
-    let contiguous_low_bound = (contiguous_high_bound - 2*contiguous_window_radius - 1).clamp(0, total_page_number); //Recalculate in case high bound got clamped
 
-    let contiguous_range = contiguous_low_bound..contiguous_high_bound;
+    let status = match shown_articles(table_current_page, articles_per_page, props.article_total_number) {
+        Some((first_article, last_article)) => format!("Showing {} to {} of {} entries", first_article, last_article, props.article_total_number),
+        None => "No entries to show".to_string()
+    };
+
+    let contiguous_range = contiguous_pages(table_current_page, total_page_number, 2);
+    let contiguous_low_bound = contiguous_range.start;
+    let contiguous_high_bound = contiguous_range.end;
     html! {
         <div class="row py-2" id="table_footer">
             <div class="col">
-                <div role="status" aria-live="polite">{format!("Showing {} to {} of {} entries", first_article, std::cmp::min(last_article as usize, props.article_total_number), props.article_total_number)}</div>
+                <div role="status" aria-live="polite">{status}</div>
                 <ArticlesPerPageDropdown articles_per_page={props.articles_per_page.clone()} table_current_page={props.table_current_page.clone()}/> 
             </div>
             
 
             <div class="col">
-                <div class="float-end">
-                    <ul class="pagination pagination-lg">
-                        if contiguous_low_bound != 0 {
-                            <PageItem table_current_page={props.table_current_page.clone()} page_index={0}/>
-                            if contiguous_low_bound > 1 {
-                                <li class="page-item disabled">
-                                    <a aria-disabled="true" role="link" tabindex="-1" class="page-link">{"…"}</a>
-                                </li>
+                if total_page_number > 1 {
+                    <div class="float-end">
+                        <ul class="pagination pagination-lg">
+                            <StepItem table_current_page={props.table_current_page.clone()} page_index={table_current_page - 1} disabled={table_current_page == 0} label="Previous" icon="bi bi-chevron-left"/>
+                            if contiguous_low_bound != 0 {
+                                <PageItem table_current_page={props.table_current_page.clone()} page_index={0}/>
+                                if contiguous_low_bound > 1 {
+                                    <li class="page-item disabled">
+                                        <a aria-disabled="true" role="link" tabindex="-1" class="page-link">{"…"}</a>
+                                    </li>
+                                }
                             }
-                        }
-                        
-                        if contiguous_range.len() != 1 {
-                            { contiguous_range.into_iter().map(|index| html!{<PageItem table_current_page={props.table_current_page.clone()} page_index={index}/>} ).collect::<Html>() }
-                        }
-                        
-                        if contiguous_high_bound != total_page_number {
-                            if total_page_number-contiguous_low_bound > 1 {
-                                <li class="page-item disabled"><a aria-disabled="true" role="link" tabindex="-1" class="page-link">{"…"}</a></li>
+                            
+                            { contiguous_range.map(|index| html!{<PageItem table_current_page={props.table_current_page.clone()} page_index={index}/>} ).collect::<Html>() }
+                            
+                            if contiguous_high_bound != total_page_number {
+                                if last_page_index > contiguous_high_bound {
+                                    <li class="page-item disabled"><a aria-disabled="true" role="link" tabindex="-1" class="page-link">{"…"}</a></li>
+                                }
+                                <PageItem table_current_page={props.table_current_page.clone()} page_index={last_page_index}/>
                             }
-                            <PageItem table_current_page={props.table_current_page.clone()} page_index={last_page_index}/>
-                        }
-                    </ul>
-                </div>
+                            <StepItem table_current_page={props.table_current_page.clone()} page_index={table_current_page + 1} disabled={table_current_page >= last_page_index} label="Next" icon="bi bi-chevron-right"/>
+                        </ul>
+                        <GoToPage table_current_page={props.table_current_page.clone()} {total_page_number}/>
+                    </div>
+                }
             </div>
         </div>
     }
@@ -105,12 +146,7 @@ fn articles_per_page_dropdown(props: &ArticlesPerPageDropdownItemProps) -> Html
             articles_per_page.set(value);
 
             event.prevent_default();
-            let element = gloo_utils::document()
-                .get_element_by_id("table")
-                .and_then(|element| element.dyn_into::<HtmlElement>().ok());
-            if let Some(element) = element {
-                element.scroll_into_view();
-            }
+            scroll_to_table();
         })
     };
 
@@ -133,12 +169,7 @@ fn page_item(props: &PageItemProps) -> Html {
             table_current_page.set(page_index);
 
             event.prevent_default();
-            let element = gloo_utils::document()
-                .get_element_by_id("table")
-                .and_then(|element| element.dyn_into::<HtmlElement>().ok());
-            if let Some(element) = element {
-                element.scroll_into_view();
-            }
+            scroll_to_table();
         })
     };
 
@@ -150,4 +181,140 @@ fn page_item(props: &PageItemProps) -> Html {
     html! {
         <li class={class}><button class="page-link" {onclick}>{props.page_index+1}</button></li>
     }
-}
\ No newline at end of file
+}
+
+#[derive(Clone, PartialEq, Properties)]
+struct StepItemProps {
+    table_current_page: UseStateHandle<i32>,
+    page_index: i32,
+    disabled: bool,
+    label: AttrValue,
+    icon: AttrValue
+}
+#[function_component(StepItem)]
+fn step_item(props: &StepItemProps) -> Html {
+    let onclick = {
+        let table_current_page = props.table_current_page.clone();
+        let page_index = props.page_index;
+        Callback::from(move |event: MouseEvent| {
+            table_current_page.set(page_index);
+
+            event.prevent_default();
+            scroll_to_table();
+        })
+    };
+
+    let class = match props.disabled {
+        true => "page-item disabled",
+        false => "page-item"
+    };
+
+    html! {
+        <li class={class}>
+            <button class="page-link" aria-label={props.label.clone()} disabled={props.disabled} {onclick}><i class={props.icon.clone()}></i></button>
+        </li>
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+struct GoToPageProps {
+    table_current_page: UseStateHandle<i32>,
+    total_page_number: i32
+}
+#[function_component(GoToPage)]
+fn go_to_page(props: &GoToPageProps) -> Html {
+    let input_node_ref = use_node_ref();
+    let onsubmit = {
+        let table_current_page = props.table_current_page.clone();
+        let input_node_ref = input_node_ref.clone();
+        let total_page_number = props.total_page_number;
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+            let page_number = crate::common::get_value(&input_node_ref)
+                .and_then(|value| value.trim().parse::<i32>().ok());
+            if let Some(page_number) = page_number {
+                table_current_page.set(page_number.clamp(1, total_page_number) - 1);
+                scroll_to_table();
+            }
+        })
+    };
+
+    html! {
+        <form class="input-group justify-content-end" {onsubmit}>
+            <input type="number" class="form-control flex-grow-0" style="width: 6rem" min="1" max={props.total_page_number.to_string()} placeholder="Page" aria-label="Page number" ref={input_node_ref}/>
+            <button type="submit" class="btn btn-outline-secondary">{"Go to page"}</button>
+        </form>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_count_rounds_up_partial_pages() {
+        assert_eq!(page_count(25, 10), 3);
+        assert_eq!(page_count(1, 10), 1);
+    }
+
+    #[test]
+    fn page_count_exact_multiple_has_no_extra_page() {
+        assert_eq!(page_count(30, 10), 3);
+        assert_eq!(page_count(10, 10), 1);
+    }
+
+    #[test]
+    fn page_count_without_results_or_page_size() {
+        assert_eq!(page_count(0, 10), 0);
+        assert_eq!(page_count(25, 0), 0);
+        assert_eq!(page_count(25, -5), 0);
+    }
+
+    #[test]
+    fn shown_articles_on_full_and_last_pages() {
+        assert_eq!(shown_articles(0, 10, 25), Some((1, 10)));
+        assert_eq!(shown_articles(2, 10, 25), Some((21, 25)));
+        assert_eq!(shown_articles(2, 10, 30), Some((21, 30)));
+    }
+
+    #[test]
+    fn shown_articles_without_results() {
+        assert_eq!(shown_articles(0, 10, 0), None);
+    }
+
+    #[test]
+    fn shown_articles_past_the_end_or_invalid() {
+        assert_eq!(shown_articles(3, 10, 25), None);
+        assert_eq!(shown_articles(3, 10, 30), None);
+        assert_eq!(shown_articles(-1, 10, 25), None);
+        assert_eq!(shown_articles(0, 0, 25), None);
+        assert_eq!(shown_articles(0, -10, 25), None);
+    }
+
+    #[test]
+    fn contiguous_pages_centered_in_the_middle() {
+        assert_eq!(contiguous_pages(10, 20, 2), 8..13);
+    }
+
+    #[test]
+    fn contiguous_pages_clamped_near_both_ends() {
+        assert_eq!(contiguous_pages(0, 20, 2), 0..5);
+        assert_eq!(contiguous_pages(1, 20, 2), 0..5);
+        assert_eq!(contiguous_pages(19, 20, 2), 15..20);
+        assert_eq!(contiguous_pages(18, 20, 2), 15..20);
+    }
+
+    #[test]
+    fn contiguous_pages_with_fewer_pages_than_the_window() {
+        assert_eq!(contiguous_pages(0, 3, 2), 0..3);
+        assert_eq!(contiguous_pages(2, 3, 2), 0..3);
+        assert_eq!(contiguous_pages(0, 1, 2), 0..1);
+        assert_eq!(contiguous_pages(0, 0, 2), 0..0);
+    }
+
+    #[test]
+    fn contiguous_pages_past_the_end() {
+        assert_eq!(contiguous_pages(7, 3, 2), 0..3);
+        assert_eq!(contiguous_pages(25, 20, 2), 15..20);
+    }
+}
@@ -0,0 +1,131 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::table::Article;
+
+const WIDTH: f64 = 1000.0;
+const HEIGHT: f64 = 120.0;
+const AXIS_HEIGHT: f64 = 20.0;
+
+pub fn year_counts(articles: &[Article]) -> BTreeMap<i32, usize> {
+    let mut counts = BTreeMap::new();
+    for year in articles.iter().filter_map(|article| article.year_published) {
+        *counts.entry(year).or_insert(0) += 1;
+    }
+    if let (Some(first), Some(last)) = (counts.keys().next().copied(), counts.keys().last().copied()) {
+        for year in first..=last {
+            counts.entry(year).or_insert(0);
+        }
+    }
+    counts
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct YearHistogramProps {
+    pub articles: Rc<RefCell<Vec<Article>>>,
+    pub articles_version: u32,
+    pub year_range: Option<(i32, i32)>,
+    pub on_brush: Callback<Option<(i32, i32)>>
+}
+
+#[function_component(YearHistogram)]
+pub fn year_histogram(props: &YearHistogramProps) -> Html {
+    let counts = {
+        let articles = props.articles.clone();
+        use_memo((Rc::as_ptr(&props.articles) as usize, props.articles_version), move |_| year_counts(&articles.borrow()))
+    };
+    let brush = use_state(|| None::<(i32, i32)>);
+
+    let onmouseup = {
+        let brush = brush.clone();
+        let on_brush = props.on_brush.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some((start, end)) = *brush {
+                on_brush.emit(Some((start.min(end), start.max(end))));
+            }
+            brush.set(None);
+        })
+    };
+    let onmouseleave = {
+        let brush = brush.clone();
+        Callback::from(move |_: MouseEvent| {
+            brush.set(None);
+        })
+    };
+    let onclear = {
+        let on_brush = props.on_brush.clone();
+        Callback::from(move |_: MouseEvent| {
+            on_brush.emit(None);
+        })
+    };
+
+    let (Some(first_year), Some(max_count)) = (counts.keys().next().copied(), counts.values().max().copied()) else {
+        return html! {};
+    };
+
+    let bar_width = WIDTH / counts.len() as f64;
+    let highlighted = match *brush {
+        Some((start, end)) => Some((start.min(end), start.max(end))),
+        None => props.year_range
+    };
+    let label_step = match counts.len() {
+        0..=20 => 1,
+        21..=60 => 5,
+        _ => 10
+    };
+
+    let bars = counts.iter().map(|(year, count)| {
+        let year = *year;
+        let x = (year - first_year) as f64 * bar_width;
+        let height = (HEIGHT - AXIS_HEIGHT) * *count as f64 / max_count.max(1) as f64;
+        let selected = highlighted.is_none_or(|(from, to)| (from..=to).contains(&year));
+
+        let onmousedown = {
+            let brush = brush.clone();
+            Callback::from(move |event: MouseEvent| {
+                event.prevent_default();
+                brush.set(Some((year, year)));
+            })
+        };
+        let onmouseenter = {
+            let brush = brush.clone();
+            Callback::from(move |_: MouseEvent| {
+                if let Some((start, _)) = *brush {
+                    brush.set(Some((start, year)));
+                }
+            })
+        };
+
+        html! {
+            <g>
+                <rect x={x.to_string()} y={(HEIGHT - AXIS_HEIGHT - height).to_string()} width={(bar_width * 0.9).to_string()} height={height.to_string()}
+                    fill="var(--bs-primary)" fill-opacity={if selected { "0.8" } else { "0.2" }}/>
+                if year % label_step == 0 {
+                    <text x={(x + bar_width / 2.0).to_string()} y={(HEIGHT - 5.0).to_string()} font-size="11" text-anchor="middle" fill="var(--bs-body-color)">{year}</text>
+                }
+                <rect x={x.to_string()} y="0" width={bar_width.to_string()} height={(HEIGHT - AXIS_HEIGHT).to_string()} fill="transparent"
+                    {onmousedown} {onmouseenter}>
+                    <title>{format!("{year} : {count} articles")}</title>
+                </rect>
+            </g>
+        }
+    }).collect::<Html>();
+
+    html! {
+        <div class="mb-3">
+            <div class="d-flex align-items-center gap-2">
+                <small class="text-body-secondary">{"Publication years — drag over the bars to filter the table"}</small>
+                if let Some((from, to)) = props.year_range {
+                    <span class="badge text-bg-primary">{format!("{from} – {to}")}</span>
+                    <button class="btn btn-outline-secondary btn-sm" onclick={onclear}>{"Clear"}</button>
+                }
+            </div>
+            <svg class="year-histogram" viewBox={format!("0 0 {WIDTH} {HEIGHT}")} preserveAspectRatio="none" {onmouseup} {onmouseleave}>
+                {bars}
+            </svg>
+        </div>
+    }
+}
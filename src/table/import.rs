@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use yew::prelude::*;
+
+use crate::api::SnowballResults;
+use crate::common::Error;
+use crate::table::{Article, SearchResults};
+use crate::table::article::Metric;
+use crate::table::download::LIST_SEPARATOR;
+use crate::session::Session;
+
+// Headers of the CSV exports which serialized the articles directly
+const LEGACY_HEADERS: [(&str, &str); 7] = [
+    ("first_author", "First author"),
+    ("year_published", "Year published"),
+    ("journal", "Journal"),
+    ("title", "Title"),
+    ("summary", "Summary"),
+    ("citations", "Citations"),
+    ("score", "Score"),
+];
+
+fn import_error(error: impl std::fmt::Display) -> Error {
+    Error::Import(error.to_string())
+}
+
+fn article_from_record(record: &HashMap<&str, String>) -> Article {
+    let text = |header: &str| record.get(header).map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+    // Excel may turn the numbers written as text back into floats
+    let number = |header: &str| text(header).and_then(|value| value.parse::<f64>().ok()).map(|value| value.round() as i32);
+    // Older Excel exports wrote missing years and citation counts as 0
+    let known_number = |header: &str| number(header).filter(|value| *value != 0);
+    let list = |header: &str| text(header).map(|value| value.split(LIST_SEPARATOR).map(str::to_string).collect::<Vec<_>>());
+
+    Article {
+        first_author: text("First author"),
+        year_published: known_number("Year published"),
+        journal: text("Journal"),
+        title: text("Title"),
+        summary: text("Summary"),
+        doi: text("doi"),
+        citations: known_number("Citations"),
+        score: number("Score"),
+        pmid: text("PMID"),
+        pmcid: text("PMCID"),
+        lens_id: text("Lens ID"),
+        authors: list("Authors"),
+        publication_type: text("Publication type"),
+        volume: text("Volume"),
+        issue: text("Issue"),
+        pages: text("Pages"),
+        language: text("Language"),
+        keywords: list("Keywords"),
+        score_per_seed: text("Score per seed").and_then(|value| value.parse().ok()).map(Metric),
+        sources: list("Sources"),
+        max_score: number("Max score"),
+        ..Default::default()
+    }
+}
+
+fn articles_from_rows(header: Vec<String>, rows: impl Iterator<Item = Vec<String>>) -> Result<Vec<Article>, Error> {
+    let header = header
+        .into_iter()
+        .map(|column| match LEGACY_HEADERS.iter().find(|(legacy, _)| *legacy == column) {
+            Some((_, current)) => current.to_string(),
+            None => column
+        })
+        .collect::<Vec<_>>();
+    if !header.iter().any(|column| column == "Title") || !header.iter().any(|column| column == "Score") {
+        return Err(Error::Import("the Title and Score columns are missing, this is not a BibliZap export".to_string()));
+    }
+
+    Ok(rows
+        .map(|row| {
+            let record = header.iter().map(String::as_str).zip(row).collect::<HashMap<_, _>>();
+            article_from_record(&record)
+        })
+        .collect())
+}
+
+fn from_excel(bytes: &[u8]) -> Result<Vec<Article>, Error> {
+    use calamine::{Reader, Xlsx};
+
+    let mut workbook = Xlsx::new(Cursor::new(bytes)).map_err(import_error)?;
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or_else(|| Error::Import("the workbook has no worksheet".to_string()))?
+        .map_err(import_error)?;
+
+    let mut rows = range.rows().map(|row| row.iter().map(|cell| cell.to_string()).collect::<Vec<_>>());
+    let header = rows.next().unwrap_or_default();
+    articles_from_rows(header, rows)
+}
+
+fn from_csv(bytes: &[u8]) -> Result<Vec<Article>, Error> {
+    let mut reader = csv::Reader::from_reader(bytes);
+    let header = reader.headers().map_err(import_error)?.iter().map(str::to_string).collect::<Vec<_>>();
+    let rows = reader
+        .records()
+        .map(|record| Ok(record.map_err(import_error)?.iter().map(str::to_string).collect()))
+        .collect::<Result<Vec<_>, Error>>()?;
+    articles_from_rows(header, rows.into_iter())
+}
+
+fn from_json(bytes: &[u8]) -> Result<Vec<Article>, Error> {
+    Ok(Session::from_json(bytes)?.articles)
+}
+
+pub fn parse_export(filename: &str, bytes: &[u8]) -> Result<Vec<Article>, Error> {
+    let extension = filename.rsplit_once('.').map(|(_, extension)| extension.to_lowercase()).unwrap_or_default();
+    match extension.as_str() {
+        "xlsx" => from_excel(bytes),
+        "csv" => from_csv(bytes),
+        "json" => from_json(bytes),
+        _ => Err(Error::Import(format!("unsupported file type \"{filename}\"")))
+    }
+}
+
+fn seed_count(articles: &[Article]) -> usize {
+    articles
+        .iter()
+        .find_map(|article| Some(article.score? as f64 / article.score_per_seed?.0))
+        .filter(|count| count.is_finite())
+        .map(|count| count.round() as usize)
+        .unwrap_or_default()
+}
+
+pub fn imported_results(filename: &str, bytes: &[u8]) -> Result<SearchResults, Error> {
+    if filename.to_lowercase().ends_with(".json") {
+        return Ok(Session::from_json(bytes)?.into_results(filename));
+    }
+
+    let articles = parse_export(filename, bytes)?;
+    let seed_count = seed_count(&articles);
+    let mut results = SearchResults::new(SnowballResults { articles, ..Default::default() }, seed_count, None);
+    results.imported_from = Some(filename.to_string());
+    Ok(results)
+}
+
+pub async fn read_file(input: &web_sys::HtmlInputElement) -> Option<(String, Result<Vec<u8>, Error>)> {
+    let file = gloo_file::File::from(input.files()?.get(0)?);
+    let bytes = gloo_file::futures::read_as_bytes(&file).await.map_err(import_error);
+    Some((file.name(), bytes))
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct ImportExportProps {
+    pub on_requesting_table: Callback<()>,
+    pub on_receiving_response: Callback<Result<SearchResults, Error>>
+}
+
+#[function_component(ImportExport)]
+pub fn import_export(props: &ImportExportProps) -> Html {
+    let onchange = {
+        let on_requesting_table = props.on_requesting_table.clone();
+        let on_receiving_response = props.on_receiving_response.clone();
+        Callback::from(move |event: Event| {
+            let input = event.target_unchecked_into::<web_sys::HtmlInputElement>();
+            let on_requesting_table = on_requesting_table.clone();
+            let on_receiving_response = on_receiving_response.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let Some((filename, bytes)) = read_file(&input).await else { return };
+                on_requesting_table.emit(());
+                on_receiving_response.emit(bytes.and_then(|bytes| imported_results(&filename, &bytes)));
+                // Allows opening the same file again
+                input.set_value("");
+            });
+        })
+    };
+
+    html! {
+        <div class="container-md mb-4">
+            <label class="form-label" for="importInput">{"Or open articles or a session downloaded from BibliZap"}</label>
+            <input class="form-control" type="file" id="importInput" accept=".xlsx,.csv,.json" {onchange}/>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::download::{to_csv, to_excel, to_json};
+
+    fn exported_article() -> Article {
+        let mut article = Article {
+            title: Some("A title".to_string()),
+            score: Some(42),
+            citations: Some(3),
+            year_published: Some(2020),
+            authors: Some(vec!["Smith, John".to_string(), "Doe, Jane".to_string()]),
+            keywords: Some(vec!["imaging".to_string()]),
+            doi: Some("10.1/abc".to_string()),
+            ..Default::default()
+        };
+        crate::table::article::compute_metrics(std::slice::from_mut(&mut article), 3);
+        article
+    }
+
+    #[test]
+    fn exports_round_trip() {
+        let article = exported_article();
+        let exports = [
+            ("BibliZap.xlsx", to_excel(std::slice::from_ref(&article)).unwrap()),
+            ("BibliZap.csv", to_csv(std::slice::from_ref(&article)).unwrap()),
+            ("BibliZap.json", to_json(std::slice::from_ref(&article)).unwrap())
+        ];
+        for (filename, bytes) in exports {
+            let imported = parse_export(filename, &bytes).unwrap();
+            assert_eq!(imported.len(), 1, "{filename}");
+            assert_eq!(imported[0].title, article.title, "{filename}");
+            assert_eq!(imported[0].score, article.score, "{filename}");
+            assert_eq!(imported[0].year_published, article.year_published, "{filename}");
+            assert_eq!(imported[0].authors, article.authors, "{filename}");
+            assert_eq!(imported[0].keywords, article.keywords, "{filename}");
+            assert_eq!(seed_count(&imported), 3, "{filename}");
+        }
+    }
+
+    // Written the way the exports of the first releases were
+    fn baseline_excel() -> Vec<u8> {
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        let rows = [
+            ["doi", "Title", "Journal", "Year published", "Summary", "Citations", "Score"],
+            ["10.1/abc", "A title", "A journal", "2020", "A summary", "3", "42"],
+            ["", "Undated", "", "0", "", "0", "7"]
+        ];
+        for (row, values) in rows.iter().enumerate() {
+            for (column, value) in values.iter().enumerate() {
+                worksheet.write_string(row as u32, column as u16, *value).unwrap();
+            }
+        }
+        workbook.save_to_buffer().unwrap()
+    }
+
+    #[test]
+    fn imports_baseline_exports() {
+        let csv = b"first_author,year_published,journal,title,summary,doi,citations,score\n\
+            Smith,2020,A journal,A title,A summary,10.1/abc,3,42\n\
+            ,,,Undated,,,,7\n";
+        let exports = [("BibliZap.xlsx", baseline_excel()), ("BibliZap.csv", csv.to_vec())];
+        for (filename, bytes) in exports {
+            let imported = parse_export(filename, &bytes).unwrap();
+            assert_eq!(imported.len(), 2, "{filename}");
+            assert_eq!(imported[0].title.as_deref(), Some("A title"), "{filename}");
+            assert_eq!(imported[0].journal.as_deref(), Some("A journal"), "{filename}");
+            assert_eq!(imported[0].doi.as_deref(), Some("10.1/abc"), "{filename}");
+            assert_eq!(imported[0].year_published, Some(2020), "{filename}");
+            assert_eq!(imported[0].citations, Some(3), "{filename}");
+            assert_eq!(imported[0].score, Some(42), "{filename}");
+            assert_eq!(imported[1].year_published, None, "{filename}");
+            assert_eq!(imported[1].citations, None, "{filename}");
+            assert_eq!(imported[1].score, Some(7), "{filename}");
+        }
+    }
+
+    #[test]
+    fn rejects_files_which_are_not_exports() {
+        assert!(matches!(parse_export("notes.txt", b""), Err(Error::Import(_))));
+        assert!(matches!(parse_export("other.csv", b"a,b\n1,2\n"), Err(Error::Import(_))));
+    }
+}
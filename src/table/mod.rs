@@ -1,27 +1,128 @@
-use std::{cell::RefCell, ops::DerefMut};
+use std::{cell::RefCell, collections::{BTreeMap, HashSet}, ops::DerefMut};
 use std::ops::Deref;
 use std::rc::Rc;
 
+use gloo_timers::callback::Timeout;
+use wasm_bindgen::JsCast;
+use serde::{Deserialize, Serialize};
 use yew::prelude::*;
 
+use crate::graph::CitationGraph;
+use crate::api::{CitationEdge, MAX_RETRIES, RetryAttempt, SeedResolution, SnowballParameters, SnowballProgress, SnowballResults, SnowballUpdate};
+use crate::common::{ErrorReport, RecoveryAction, SearchFor, copy_to_clipboard};
+use crate::session::Session;
+
 pub mod article;
 pub use article::Article;
 
 mod filter;
-use filter::Filters;
+pub use filter::{Filters, build_index, filter_articles, normalize, FILTER_DEBOUNCE_MS};
+
+mod window;
 
 mod footer;
 use footer::TableFooter;
 
-mod download;
+pub mod download;
 use download::*;
 
+pub mod import;
+
+mod selection;
+use selection::{Selection, SelectionAction, SelectionToolbar};
+
+mod histogram;
+use histogram::YearHistogram;
+
+mod statistics;
+use statistics::{Facet, StatisticsPanel};
+
+mod scores;
+use scores::ScoreDistribution;
+
+pub mod duplicates;
+use duplicates::DuplicatesPanel;
+
+mod seeds;
+use seeds::SeedReport;
+
+mod detail;
+use detail::ArticleDetail;
+
+mod screening;
+pub use screening::Decision;
+use screening::{DecisionButtons, NoteInput, Screening, ScreeningAction};
+
 #[derive(Clone, PartialEq)]
 pub enum TableStatus {
     NotRequested,
-    Requested,
-    RequestError(String),
-    Available(Rc<RefCell<Vec<Article>>>)
+    Requested(Option<SnowballProgress>),
+    Retrying(RetryAttempt),
+    RequestError(ErrorReport),
+    Available(SearchResults)
+}
+
+#[derive(Clone, PartialEq)]
+pub struct SearchResults {
+    pub articles: Rc<RefCell<Vec<Article>>>,
+    pub seeds: Rc<Vec<SeedResolution>>,
+    pub edges: Rc<Vec<CitationEdge>>,
+    pub cached_at_ms: Option<f64>,
+    pub sources: Rc<Vec<String>>,
+    pub imported_from: Option<String>,
+    pub parameters: Option<SnowballParameters>,
+    pub restored: Option<Rc<TableState>>
+}
+
+impl SearchResults {
+    pub fn new(results: SnowballResults, input_count: usize, cached_at_ms: Option<f64>) -> Self {
+        let SnowballResults { mut articles, seeds, edges } = results;
+
+        articles.sort_by_key(|article| std::cmp::Reverse(article.score.unwrap_or_default()));
+        article::assign_ids(&mut articles);
+        // Older backends do not report the seeds, which are then assumed to be all resolved
+        let seed_count = match seeds.is_empty() {
+            true => input_count,
+            false => seeds.iter().filter(|seed| seed.resolved).count()
+        };
+        article::compute_metrics(&mut articles, seed_count);
+
+        SearchResults {
+            articles: Rc::new(RefCell::new(articles)),
+            seeds: Rc::new(seeds),
+            edges: Rc::new(edges),
+            cached_at_ms,
+            sources: Rc::new(Vec::new()),
+            imported_from: None,
+            parameters: None,
+            restored: None
+        }
+    }
+}
+
+pub struct StatusCallbacks {
+    pub on_submit_error: Callback<crate::common::Error>,
+    pub on_requesting_table: Callback<()>,
+    pub on_update: Callback<SnowballUpdate>,
+    pub on_receiving_response: Callback<Result<SearchResults, crate::common::Error>>
+}
+
+impl StatusCallbacks {
+    pub fn new(set_status: Callback<TableStatus>) -> Self {
+        StatusCallbacks {
+            on_submit_error: set_status.reform(|error: crate::common::Error| TableStatus::RequestError(error.report())),
+            on_requesting_table: set_status.reform(|_: ()| TableStatus::Requested(None)),
+            on_update: set_status.reform(|update: SnowballUpdate| match update {
+                SnowballUpdate::Progress(progress) => TableStatus::Requested(Some(progress)),
+                SnowballUpdate::Retrying(retry) => TableStatus::Retrying(retry)
+            }),
+            on_receiving_response: set_status.reform(|results: Result<SearchResults, crate::common::Error>| match results {
+                Ok(results) => TableStatus::Available(results),
+                Err(crate::common::Error::Cancelled) => TableStatus::NotRequested,
+                Err(error) => TableStatus::RequestError(error.report())
+            })
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, Properties)]
@@ -30,16 +131,119 @@ pub struct TableContainerProps {
 }
 #[function_component(TableContainer)]
 pub fn table_container(props: &TableContainerProps) -> Html  {
+    let show_graph = use_state(|| false);
+    let focused_article = use_state(|| None::<usize>);
+    let on_select = {
+        let show_graph = show_graph.clone();
+        let focused_article = focused_article.clone();
+        Callback::from(move |article_id: usize| {
+            focused_article.set(Some(article_id));
+            show_graph.set(false);
+        })
+    };
+    let show_view = |graph: bool| {
+        let show_graph = show_graph.clone();
+        let focused_article = focused_article.clone();
+        Callback::from(move |_: MouseEvent| {
+            focused_article.set(None);
+            show_graph.set(graph);
+        })
+    };
+
+    let save_session = |results: &SearchResults| {
+        let results = results.clone();
+        Callback::from(move |state: TableState| {
+            let timestamp = chrono::Local::now().to_rfc3339();
+            let saved = Session::new(&results, state)
+                .to_json()
+                .and_then(|bytes| download_bytes_as_file(&bytes, &format!("BibliZap-session-{timestamp}.json")));
+            if let Err(error) = saved {
+                gloo_console::log!(format!("{error}"));
+            }
+        })
+    };
+
     let content = match props.table_status.deref() {
         TableStatus::NotRequested => { html! { } }
-        TableStatus::Available(articles) => { html! {<Table articles={articles}/>} }
-        TableStatus::Requested => { html! {<Spinner/>} }
-        TableStatus::RequestError(msg) =>  { html! {<Error msg={msg.to_owned()}/>} }
+        TableStatus::Available(results) => { html! {
+            <>
+                if let Some(cached_at_ms) = results.cached_at_ms {
+                    <CachedNotice {cached_at_ms}/>
+                }
+                if let Some(filename) = results.imported_from.clone() {
+                    <div class="container-md alert alert-info" role="status">
+                        {format!("Articles read from {filename}, no search was sent to the BibliZap server.")}
+                        if let Some(parameters) = results.parameters.as_ref() {
+                            <div class="small mt-1">{describe_parameters(parameters)}</div>
+                        }
+                    </div>
+                }
+                <CombinedNotice sources={results.sources.clone()}/>
+                <SeedReport seeds={results.seeds.clone()}/>
+                if !results.edges.is_empty() {
+                    <ul class="nav nav-tabs container-fluid mb-3">
+                        <li class="nav-item">
+                            <button class={classes!("nav-link", (!*show_graph).then_some("active"))} onclick={show_view(false)}>
+                                <i class="bi bi-table me-2"></i>{"Table"}
+                            </button>
+                        </li>
+                        <li class="nav-item">
+                            <button class={classes!("nav-link", show_graph.then_some("active"))} onclick={show_view(true)}>
+                                <i class="bi bi-diagram-3 me-2"></i>{"Citation network"}
+                            </button>
+                        </li>
+                    </ul>
+                    if *show_graph {
+                        <CitationGraph results={results.clone()} {on_select}/>
+                    }
+                }
+                // Hidden rather than unmounted so that filters, sorting and pagination survive switching views
+                <div class={classes!(show_graph.then_some("d-none"))}>
+                    <Table articles={results.articles.clone()} focused_article={*focused_article}
+                        restored={results.restored.clone()} on_save_session={save_session(results)}/>
+                </div>
+            </>
+        } }
+        TableStatus::Requested(None) => { html! {<Spinner/>} }
+        TableStatus::Requested(Some(progress)) => { html! {<Progress progress={progress.clone()}/>} }
+        TableStatus::Retrying(retry) => { html! {<Retrying retry={retry.clone()}/>} }
+        TableStatus::RequestError(report) =>  { html! {<Error report={report.clone()}/>} }
     };
 
     content
 }
 
+fn describe_parameters(parameters: &SnowballParameters) -> String {
+    let search_for = match parameters.search_for {
+        SearchFor::References => "references",
+        SearchFor::Citations => "citations",
+        SearchFor::Both => "references and citations"
+    };
+    format!("Saved search: {} — {search_for} at depth {}, up to {} results",
+        parameters.input_id_list.join(" "), parameters.depth, parameters.output_max_size)
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct CombinedNoticeProps {
+    sources: Rc<Vec<String>>,
+}
+
+#[function_component(CombinedNotice)]
+fn combined_notice(props: &CombinedNoticeProps) -> Html {
+    if props.sources.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div class="container-md alert alert-info" role="status">
+            {format!("{} searches were combined : the score of an article is the sum of its scores, the Sources column telling which searches found it.", props.sources.len())}
+            <ul class="mb-0 mt-2">
+                { props.sources.iter().map(|source| html! { <li>{source}</li> }).collect::<Html>() }
+            </ul>
+        </div>
+    }
+}
+
 #[function_component(Spinner)]
 pub fn spinner() -> Html {
     html! {
@@ -53,26 +257,149 @@ pub fn spinner() -> Html {
     }
 }
 
+#[derive(Clone, PartialEq, Properties)]
+pub struct CachedNoticeProps {
+    cached_at_ms: f64,
+}
+
+#[function_component(CachedNotice)]
+pub fn cached_notice(props: &CachedNoticeProps) -> Html {
+    let cached_at = chrono::DateTime::from_timestamp_millis(props.cached_at_ms as i64)
+        .map(|date| date.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+
+    html! {
+        <div class="container-md">
+            <div class="alert alert-info" role="status">
+                <i class="bi bi-database me-2"></i>
+                {format!("These results were cached in your browser on {cached_at}. Tick \"Force refresh\" to search again.")}
+            </div>
+        </div>
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct ProgressProps {
+    progress: SnowballProgress,
+}
+
+#[function_component(Progress)]
+pub fn progress(props: &ProgressProps) -> Html {
+    let progress = &props.progress;
+    let level = match (progress.level, progress.depth) {
+        (Some(level), Some(depth)) => format!(" (level {level}/{depth})"),
+        (Some(level), None) => format!(" (level {level})"),
+        _ => String::new()
+    };
+    let counts = match (progress.done, progress.total) {
+        (Some(done), Some(total)) => format!(" : {done} / {total}"),
+        (Some(done), None) => format!(" : {done}"),
+        _ => String::new()
+    };
+    let percent = match (progress.done, progress.total) {
+        (Some(done), Some(total)) if total > 0 => Some(100 * done.min(total) / total),
+        _ => None
+    };
+
+    html! {
+        <div class="container-md" style="margin-bottom: 50px;">
+            <div class="d-flex align-items-center mb-2" role="status" aria-live="polite">
+                <div class="spinner-border spinner-border-sm me-2" aria-hidden="true"></div>
+                <strong>{format!("{}{level}{counts}", progress.stage)}</strong>
+            </div>
+            if let Some(percent) = percent {
+                <div class="progress" role="progressbar" aria-valuenow={percent.to_string()} aria-valuemin="0" aria-valuemax="100">
+                    <div class="progress-bar" style={format!("width: {percent}%")}></div>
+                </div>
+            }
+        </div>
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct RetryingProps {
+    retry: RetryAttempt,
+}
+
+#[function_component(Retrying)]
+pub fn retrying(props: &RetryingProps) -> Html {
+    html! {
+        <div class="container-md" style="margin-bottom: 50px;">
+            <div class="d-flex align-items-center" role="status" aria-live="polite">
+                <div class="spinner-border spinner-border-sm me-2" aria-hidden="true"></div>
+                <strong>{format!("Retrying ({}/{MAX_RETRIES})…", props.retry.retry)}</strong>
+            </div>
+            <div class="form-text">{format!("{} — next attempt in {:.0} s", props.retry.reason, (props.retry.delay_ms as f64 / 1000.0).ceil())}</div>
+        </div>
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TableState {
+    pub filters: Filters,
+    pub global_filter: String,
+    pub selected: Vec<usize>,
+    pub current_page: i32,
+    pub articles_per_page: i32,
+    pub sort: Option<SortState>,
+    pub notes: BTreeMap<usize, String>,
+    pub decisions: BTreeMap<usize, Decision>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SortState {
+    pub column: String,
+    pub descending: bool,
+}
+
+impl Default for TableState {
+    fn default() -> Self {
+        TableState {
+            filters: Filters::default(),
+            global_filter: String::new(),
+            selected: Vec::new(),
+            current_page: 0,
+            articles_per_page: 10,
+            sort: None,
+            notes: BTreeMap::new(),
+            decisions: BTreeMap::new(),
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Properties)]
 pub struct TableProps {
-    articles: Rc<RefCell<Vec<Article>>>,
+    pub articles: Rc<RefCell<Vec<Article>>>,
+    #[prop_or_default]
+    pub focused_article: Option<usize>,
+    /// State applied once when it changes, the sort order being the order of `articles`
+    #[prop_or_default]
+    pub restored: Option<Rc<TableState>>,
+    #[prop_or_default]
+    pub on_save_session: Option<Callback<TableState>>,
 }
 
 #[function_component(Table)]
 pub fn table(props: &TableProps) -> Html {
-    let selected_articles = use_mut_ref(Vec::<String>::new);
-    let selected_articles = use_state(|| selected_articles);
-
+    let selection = use_reducer(Selection::default);
     let update_selected = {
-        let selected_articles = selected_articles.clone();
-        Callback::from(move |element : (String, bool)| {
-            let rc = selected_articles.deref().to_owned();
-            if element.1 {
-                rc.deref().borrow_mut().push(element.0); 
-            } else {
-                rc.deref().borrow_mut().retain(|x| *x != element.0)
-            }
-            selected_articles.set(rc);
+        let dispatcher = selection.dispatcher();
+        use_callback((), move |(id, checked): (usize, bool), _| {
+            dispatcher.dispatch(SelectionAction::Set(id, checked));
+        })
+    };
+    let screening = use_reducer(Screening::default);
+    let on_decide = {
+        let dispatcher = screening.dispatcher();
+        use_callback((), move |(id, decision): (usize, Option<Decision>), _| {
+            dispatcher.dispatch(ScreeningAction::Decide(id, decision));
+        })
+    };
+    let on_note = {
+        let dispatcher = screening.dispatcher();
+        use_callback((), move |(id, note): (usize, String), _| {
+            dispatcher.dispatch(ScreeningAction::Note(id, note));
         })
     };
 
@@ -80,23 +407,93 @@ pub fn table(props: &TableProps) -> Html {
     let global_filter = use_state(|| "".to_string());
     let filters = use_mut_ref(Filters::default);
     let filters = use_state(|| filters);
-    
-    let articles_to_display = articles
-        .deref()
-        .borrow()
-        .iter()
-        .filter(|a| a.matches_global(&global_filter))
-        .filter(|a| a.matches(&filters.deref().borrow()))
-        .cloned()
-        .collect::<Vec<_>>();
-
-    let on_download_click = {
+
+    // Bumped every time the articles are reordered or the filters are edited in place
+    let articles_version = use_mut_ref(|| 0u32);
+    let filters_version = use_mut_ref(|| 0u32);
+    let trigger_update = use_force_update();
+    let redraw_table = {
+        let articles_version = articles_version.clone();
+        let trigger_update = trigger_update.clone();
+        use_callback((), move |_: (), _| {
+            *articles_version.borrow_mut() += 1;
+            trigger_update.force_update();
+        })
+    };
+    let refilter_table = {
+        let filters_version = filters_version.clone();
+        use_callback((), move |_: (), _| {
+            *filters_version.borrow_mut() += 1;
+            trigger_update.force_update();
+        })
+    };
+
+    let on_brush = {
+        let filters = filters.clone();
+        let refilter_table = refilter_table.clone();
+        Callback::from(move |year_range: Option<(i32, i32)>| {
+            filters.deref().borrow_mut().year_range = year_range;
+            refilter_table.emit(());
+        })
+    };
+
+    let on_facet = {
+        let filters = filters.clone();
+        let refilter_table = refilter_table.clone();
+        Callback::from(move |(facet, value): (Facet, Option<String>)| {
+            let value = value.as_deref().map(normalize);
+            match facet {
+                Facet::Journal => filters.deref().borrow_mut().journal_facet = value,
+                Facet::FirstAuthor => filters.deref().borrow_mut().first_author_facet = value
+            }
+            refilter_table.emit(());
+        })
+    };
+
+    let on_cutoff = {
+        let filters = filters.clone();
+        let refilter_table = refilter_table.clone();
+        Callback::from(move |min_score: Option<i32>| {
+            filters.deref().borrow_mut().min_score = min_score;
+            refilter_table.emit(());
+        })
+    };
+
+    let on_merge = {
         let articles = articles.clone();
-        Callback::from(move |_: MouseEvent| {
-            let bytes = to_excel(articles.deref().borrow().deref()).unwrap();
+        let dispatcher = selection.dispatcher();
+        let screening_dispatcher = screening.dispatcher();
+        let redraw_table = redraw_table.clone();
+        Callback::from(move |removed: Vec<usize>| {
+            article::update_relative_metrics(&mut articles.deref().borrow_mut());
+            let removed = Rc::new(removed);
+            dispatcher.dispatch(SelectionAction::Remove(removed.clone()));
+            screening_dispatcher.dispatch(ScreeningAction::Remove(removed));
+            redraw_table.emit(());
+        })
+    };
+
+    let articles_key = (Rc::as_ptr(&articles) as usize, *articles_version.borrow());
+    let search_index = {
+        let articles = articles.clone();
+        use_memo(articles_key, move |_| build_index(articles.deref().borrow().deref()))
+    };
+    let displayed_indices = {
+        let filters = filters.deref().clone();
+        use_memo((global_filter.deref().clone(), articles_key, *filters_version.borrow()), move |(global_filter, _, _)| {
+            filter_articles(&search_index, global_filter, filters.deref().borrow().deref())
+        })
+    };
+
+    let on_download = {
+        let articles = articles.clone();
+        Callback::from(move |format: ExportFormat| {
             let timestamp = chrono::Local::now().to_rfc3339();
+            let downloaded = format
+                .export(articles.deref().borrow().deref())
+                .and_then(|bytes| download_bytes_as_file(&bytes, &format!("BibliZap-{timestamp}.{}", format.extension())));
 
-            match download_bytes_as_file(&bytes, &format!("BibliZap-{timestamp}.xlsx")) {
+            match downloaded {
                 Ok(_) => (),
                 Err(error) => {gloo_console::log!(format!("{error}"));}
             }
@@ -105,55 +502,269 @@ pub fn table(props: &TableProps) -> Html {
     
     let articles_per_page = use_state(|| 10i32);
     let table_current_page = use_state(|| 0i32);
+    // Page of a restored session, which the refiltering must not reset
+    let restored_page = use_mut_ref(|| None::<i32>);
+    {
+        let table_current_page = table_current_page.clone();
+        let articles_per_page = articles_per_page.clone();
+        let restored_page = restored_page.clone();
+        use_effect_with(displayed_indices.len(), move |len| {
+            let last_page = (footer::page_count(*len, *articles_per_page) - 1).max(0);
+            table_current_page.set(restored_page.borrow_mut().take().unwrap_or(0).min(last_page));
+        });
+    }
 
-    let first_article = (table_current_page.deref() * articles_per_page.deref()).clamp(0, articles_to_display.len() as i32) as usize;
-    let last_article = (first_article as i32 + articles_per_page.deref()).clamp(0, articles_to_display.len() as i32) as usize;
-    let articles_slice = &articles_to_display[first_article..last_article];
+    // Bumped when a session is restored, so that the filter inputs show the restored values
+    let restore_version = use_state(|| 0u32);
+    let last_sort = use_mut_ref(|| None::<SortState>);
+    {
+        let filters = filters.clone();
+        let global_filter = global_filter.clone();
+        let dispatcher = selection.dispatcher();
+        let articles_per_page = articles_per_page.clone();
+        let table_current_page = table_current_page.clone();
+        let refilter_table = refilter_table.clone();
+        let restore_version = restore_version.clone();
+        let restored_page = restored_page.clone();
+        let last_sort = last_sort.clone();
+        let screening_dispatcher = screening.dispatcher();
+        use_effect_with(props.restored.clone(), move |restored| {
+            let Some(state) = restored else { return };
+            *filters.deref().borrow_mut() = state.filters.clone();
+            global_filter.set(state.global_filter.clone());
+            dispatcher.dispatch(SelectionAction::Clear);
+            dispatcher.dispatch(SelectionAction::SelectAll(Rc::new(state.selected.clone())));
+            articles_per_page.set(state.articles_per_page);
+            table_current_page.set(state.current_page);
+            *restored_page.borrow_mut() = Some(state.current_page);
+            *last_sort.borrow_mut() = state.sort.clone();
+            screening_dispatcher.dispatch(ScreeningAction::Restore(state.decisions.clone(), state.notes.clone()));
+            restore_version.set(*restore_version + 1);
+            refilter_table.emit(());
+        });
+    }
+    {
+        // Runs after the page reset of the restore render, whether or not the row count changed
+        let restored_page = restored_page.clone();
+        use_effect_with(*restore_version, move |_| {
+            restored_page.borrow_mut().take();
+        });
+    }
 
-    let trigger_update = use_force_update();
-    let redraw_table = {
+    let on_save_session = props.on_save_session.clone().map(|on_save_session| {
+        let filters = filters.clone();
+        let global_filter = global_filter.clone();
+        let selection = selection.clone();
+        let articles_per_page = articles_per_page.clone();
+        let table_current_page = table_current_page.clone();
+        let last_sort = last_sort.clone();
+        let screening = screening.clone();
+        Callback::from(move |_: MouseEvent| {
+            let mut selected = selection.ids.iter().copied().collect::<Vec<_>>();
+            selected.sort_unstable();
+            on_save_session.emit(TableState {
+                filters: filters.deref().borrow().clone(),
+                global_filter: global_filter.deref().clone(),
+                selected,
+                current_page: *table_current_page,
+                articles_per_page: *articles_per_page,
+                sort: last_sort.borrow().clone(),
+                notes: screening.notes.clone(),
+                decisions: screening.decisions.clone(),
+            });
+        })
+    });
+
+    let first_article = (table_current_page.deref() * articles_per_page.deref()).clamp(0, displayed_indices.len() as i32) as usize;
+    let last_article = (first_article as i32 + articles_per_page.deref()).clamp(0, displayed_indices.len() as i32) as usize;
+    let page_indices = &displayed_indices[first_article..last_article];
+    let (page_ids, filtered_ids) = {
+        let articles = articles.deref().borrow();
+        let ids = |indices: &[usize]| Rc::new(indices.iter().map(|index| articles[*index].id).collect::<Vec<_>>());
+        (ids(page_indices), ids(&displayed_indices))
+    };
+
+    let expand_all = use_state(|| false);
+    // Rows whose summary was toggled since the last "Expand all"
+    let toggled_rows = use_state(HashSet::<usize>::new);
+    {
+        let toggled_rows = toggled_rows.clone();
+        use_effect_with(*expand_all, move |_| {
+            toggled_rows.set(HashSet::new());
+        });
+    }
+    let toggle_expanded = {
+        let toggled_rows = toggled_rows.clone();
+        use_callback(toggled_rows.clone(), move |id: usize, _| {
+            let mut toggled = toggled_rows.deref().clone();
+            if !toggled.remove(&id) {
+                toggled.insert(id);
+            }
+            toggled_rows.set(toggled);
+        })
+    };
+    let detailed_article = use_state(|| None::<Article>);
+    let show_details = {
+        let detailed_article = detailed_article.clone();
+        use_callback((), move |article: Article, _| {
+            detailed_article.set(Some(article));
+        })
+    };
+    let close_details = {
+        let detailed_article = detailed_article.clone();
         Callback::from(move |_: ()| {
-            trigger_update.force_update();
+            detailed_article.set(None);
         })
     };
     
+    let pending_scroll = use_mut_ref(|| None::<usize>);
+    {
+        let articles = articles.clone();
+        let displayed_indices = displayed_indices.clone();
+        let table_current_page = table_current_page.clone();
+        let articles_per_page = *articles_per_page;
+        let pending_scroll = pending_scroll.clone();
+        let detailed_article = detailed_article.clone();
+        use_effect_with(props.focused_article, move |focused_article| {
+            let Some(id) = *focused_article else { return };
+            let articles = articles.deref().borrow();
+            match displayed_indices.iter().position(|index| articles[*index].id == id) {
+                Some(position) => {
+                    table_current_page.set(position as i32 / articles_per_page);
+                    *pending_scroll.borrow_mut() = Some(id);
+                },
+                None => detailed_article.set(articles.iter().find(|article| article.id == id).cloned())
+            }
+        });
+    }
+    {
+        let pending_scroll = pending_scroll.clone();
+        use_effect(move || {
+            let Some(id) = *pending_scroll.borrow() else { return };
+            if let Some(element) = gloo_utils::document().get_element_by_id(&format!("article-{id}")) {
+                element.scroll_into_view();
+                pending_scroll.borrow_mut().take();
+            }
+        });
+    }
+
+    let scroll = use_state(|| (0.0f64, 1000.0f64));
+    let onscroll = {
+        let scroll = scroll.clone();
+        Callback::from(move |event: Event| {
+            let viewport = event.target_unchecked_into::<web_sys::Element>();
+            scroll.set((viewport.scroll_top() as f64, viewport.client_height() as f64));
+        })
+    };
+    // Expanded summaries make the rows much taller than window::ROW_HEIGHT
+    let virtualized = !*expand_all && toggled_rows.is_empty() && page_indices.len() > window::THRESHOLD;
+    // Renders the pending row even if it lies outside of the scrolled window
+    let scroll_top = match *pending_scroll.borrow() {
+        Some(id) if virtualized => page_ids.iter().position(|page_id| *page_id == id).map(|position| position as f64 * window::ROW_HEIGHT),
+        _ => None
+    }.unwrap_or(scroll.0);
+    let rendered_rows = match virtualized {
+        true => window::visible_rows(scroll_top, scroll.1, page_indices.len()),
+        false => 0..page_indices.len()
+    };
+    let spacer_above = rendered_rows.start as f64 * window::ROW_HEIGHT;
+    let spacer_below = (page_indices.len() - rendered_rows.end) as f64 * window::ROW_HEIGHT;
+    let show_provenance = articles.deref().borrow().iter().any(|article| article.sources.is_some());
+    let rows = {
+        let articles = articles.deref().borrow();
+        page_indices[rendered_rows]
+            .iter()
+            .map(|index| {
+                let id = articles[*index].id;
+                html!{<Row article={articles[*index].clone()} focused={props.focused_article == Some(id)} selected={selection.ids.contains(&id)} update_selected={update_selected.clone()}
+                    decision={screening.decisions.get(&id).copied()} note={screening.notes.get(&id).cloned()} on_decide={on_decide.clone()} on_note={on_note.clone()}
+                    expanded={*expand_all != toggled_rows.contains(&id)} toggle_expanded={toggle_expanded.clone()} show_details={show_details.clone()} {show_provenance}/>}
+            })
+            .collect::<Html>()
+    };
+
     html! {
         <div id="table" class="container-fluid">
             <hr/>
-            <TableGlobalSearch filter={global_filter.clone()}/>
+            <YearHistogram articles={articles.clone()} articles_version={*articles_version.borrow()} year_range={filters.deref().borrow().year_range} {on_brush}/>
+            <ScoreDistribution articles={articles.clone()} articles_version={*articles_version.borrow()} min_score={filters.deref().borrow().min_score} {on_cutoff}/>
+            <StatisticsPanel articles={articles.clone()} indices={displayed_indices.clone()}
+                journal={filters.deref().borrow().journal_facet.clone()} first_author={filters.deref().borrow().first_author_facet.clone()} on_select={on_facet}/>
+            <DuplicatesPanel articles={articles.clone()} {on_merge}/>
+            <div class="row justify-content-end align-items-end">
+                <SelectionToolbar selection={selection.clone()} {page_ids} {filtered_ids}/>
+                <ExpandAllButton expand_all={expand_all.clone()}/>
+                <TableGlobalSearch filter={global_filter.clone()} restore_version={*restore_version}/>
+            </div>
+            <div class={classes!(virtualized.then_some("table-viewport"))} onscroll={onscroll}>
             <table class="table table-hover table-bordered" style="table-layout:fixed">
                 <thead>
                     <tr>
                         <th style="width:2%"></th>
-                        <HeaderCellDoi articles={articles.clone()} redraw_table={redraw_table.clone()} style=""/>
-                        <HeaderCellTitle articles={articles.clone()} redraw_table={redraw_table.clone()} style="width:15%"/>
-                        <HeaderCellJournal articles={articles.clone()} redraw_table={redraw_table.clone()} style=""/>
-                        <HeaderCellFirstAuthor articles={articles.clone()} redraw_table={redraw_table.clone()} style=""/>
-                        <HeaderCellYearPublished articles={articles.clone()} redraw_table={redraw_table.clone()} style=""/>
-                        <HeaderCellSummary articles={articles.clone()} redraw_table={redraw_table.clone()} style="width:50%"/>
-                        <HeaderCellCitations articles={articles.clone()} redraw_table={redraw_table.clone()} style=""/>
-                        <HeaderCellScore articles={articles.clone()} redraw_table={redraw_table.clone()} style=""/>
+                        <HeaderCellDoi articles={articles.clone()} redraw_table={redraw_table.clone()} last_sort={last_sort.clone()} style=""/>
+                        <HeaderCellTitle articles={articles.clone()} redraw_table={redraw_table.clone()} last_sort={last_sort.clone()} style="width:15%"/>
+                        <HeaderCellJournal articles={articles.clone()} redraw_table={redraw_table.clone()} last_sort={last_sort.clone()} style=""/>
+                        <HeaderCellFirstAuthor articles={articles.clone()} redraw_table={redraw_table.clone()} last_sort={last_sort.clone()} style=""/>
+                        <HeaderCellYearPublished articles={articles.clone()} redraw_table={redraw_table.clone()} last_sort={last_sort.clone()} style=""/>
+                        <HeaderCellSummary articles={articles.clone()} redraw_table={redraw_table.clone()} last_sort={last_sort.clone()} style="width:38%"/>
+                        <HeaderCellCitations articles={articles.clone()} redraw_table={redraw_table.clone()} last_sort={last_sort.clone()} style=""/>
+                        <HeaderCellScore articles={articles.clone()} redraw_table={redraw_table.clone()} last_sort={last_sort.clone()} style=""/>
+                        <HeaderCellScorePerCitation articles={articles.clone()} redraw_table={redraw_table.clone()} last_sort={last_sort.clone()} style=""
+                            tooltip="Score divided by the citation count : high values point to articles close to the seeds without being heavily cited reviews"/>
+                        <HeaderCellScorePerSeed articles={articles.clone()} redraw_table={redraw_table.clone()} last_sort={last_sort.clone()} style=""
+                            tooltip="Score divided by the number of resolved seeds, comparable between searches with different numbers of seeds"/>
+                        <HeaderCellPercentileRank articles={articles.clone()} redraw_table={redraw_table.clone()} last_sort={last_sort.clone()} style=""
+                            tooltip="Percentage of the results whose score is lower than or equal to the score of this article"/>
+                        if show_provenance {
+                            <HeaderCellSources articles={articles.clone()} redraw_table={redraw_table.clone()} last_sort={last_sort.clone()} style=""
+                                tooltip="Combined searches which found this article"/>
+                            <HeaderCellMaxScore articles={articles.clone()} redraw_table={redraw_table.clone()} last_sort={last_sort.clone()} style=""
+                                tooltip="Best score of this article among the combined searches, the Score column being their sum"/>
+                        }
                     </tr>
                 </thead>
                 <thead>
                     <tr>
                         <th></th>
-                        <HeaderCellSearchDoi filters={filters.clone()} redraw_table={redraw_table.clone()}/>
-                        <HeaderCellSearchTitle filters={filters.clone()} redraw_table={redraw_table.clone()}/>
-                        <HeaderCellSearchJournal filters={filters.clone()} redraw_table={redraw_table.clone()}/>
-                        <HeaderCellSearchFirstAuthor filters={filters.clone()} redraw_table={redraw_table.clone()}/>
-                        <HeaderCellSearchYearPublished filters={filters.clone()} redraw_table={redraw_table.clone()}/>
-                        <HeaderCellSearchSummary filters={filters.clone()} redraw_table={redraw_table.clone()}/>
-                        <HeaderCellSearchCitations filters={filters.clone()} redraw_table={redraw_table.clone()}/>
-                        <HeaderCellSearchScore filters={filters.clone()} redraw_table={redraw_table.clone()}/>
+                        <HeaderCellSearchDoi filters={filters.clone()} refilter_table={refilter_table.clone()} restore_version={*restore_version}/>
+                        <HeaderCellSearchTitle filters={filters.clone()} refilter_table={refilter_table.clone()} restore_version={*restore_version}/>
+                        <HeaderCellSearchJournal filters={filters.clone()} refilter_table={refilter_table.clone()} restore_version={*restore_version}/>
+                        <HeaderCellSearchFirstAuthor filters={filters.clone()} refilter_table={refilter_table.clone()} restore_version={*restore_version}/>
+                        <HeaderCellSearchYearPublished filters={filters.clone()} refilter_table={refilter_table.clone()} restore_version={*restore_version}/>
+                        <HeaderCellSearchSummary filters={filters.clone()} refilter_table={refilter_table.clone()} restore_version={*restore_version}/>
+                        <HeaderCellSearchCitations filters={filters.clone()} refilter_table={refilter_table.clone()} restore_version={*restore_version}/>
+                        <HeaderCellSearchScore filters={filters.clone()} refilter_table={refilter_table.clone()} restore_version={*restore_version}/>
+                        <HeaderCellSearchScorePerCitation filters={filters.clone()} refilter_table={refilter_table.clone()} restore_version={*restore_version}/>
+                        <HeaderCellSearchScorePerSeed filters={filters.clone()} refilter_table={refilter_table.clone()} restore_version={*restore_version}/>
+                        <HeaderCellSearchPercentileRank filters={filters.clone()} refilter_table={refilter_table.clone()} restore_version={*restore_version}/>
+                        if show_provenance {
+                            <HeaderCellSearchSources filters={filters.clone()} refilter_table={refilter_table.clone()} restore_version={*restore_version}/>
+                            <HeaderCellSearchMaxScore filters={filters.clone()} refilter_table={refilter_table.clone()} restore_version={*restore_version}/>
+                        }
                     </tr>
                 </thead>
                 <tbody class="table-group-divider">
-                    { articles_slice.iter().map(|article| html!{<Row article={article.clone()} update_selected={update_selected.clone()}/>} ).collect::<Html>() }
+                    if spacer_above > 0.0 {
+                        <tr style={format!("height: {spacer_above}px")}></tr>
+                    }
+                    { rows }
+                    if spacer_below > 0.0 {
+                        <tr style={format!("height: {spacer_below}px")}></tr>
+                    }
                 </tbody>
             </table>
-            <TableFooter article_total_number={articles_to_display.len()} articles_per_page={articles_per_page} table_current_page={table_current_page}/>
-            <DownloadButton onclick={on_download_click}/>
+            </div>
+            <TableFooter article_total_number={displayed_indices.len()} articles_per_page={articles_per_page} table_current_page={table_current_page}/>
+            <div class="d-flex gap-2">
+                <DownloadButton {on_download}/>
+                if let Some(onclick) = on_save_session {
+                    <button class="btn btn-outline-secondary btn-lg mb-10" {onclick}><i class="bi bi-save me-2"></i>{"Save session"}</button>
+                }
+            </div>
+            if let Some(article) = detailed_article.deref() {
+                <ArticleDetail article={article.clone()} on_close={close_details}
+                    decision={screening.decisions.get(&article.id).copied()} note={screening.notes.get(&article.id).cloned()} {on_decide} {on_note}/>
+            }
         </div>
     }
 }
@@ -163,13 +774,17 @@ pub fn table(props: &TableProps) -> Html {
 struct HeaderCellProps {
     articles: Rc<RefCell<Vec<Article>>>,
     redraw_table: Callback<()>,
+    last_sort: Rc<RefCell<Option<SortState>>>,
     style: AttrValue,
+    #[prop_or_default]
+    tooltip: Option<AttrValue>,
 }
 
 #[derive(Clone, PartialEq, Properties)]
 struct HeaderCellSearchProps {
     filters: UseStateHandle<Rc<RefCell<Filters>>>,
-    redraw_table: Callback<()>,
+    refilter_table: Callback<()>,
+    restore_version: u32,
 }
 
 use paste::paste;
@@ -181,25 +796,34 @@ macro_rules! header_cell {
                 let sort_reverse = {
                     let articles = props.articles.clone();
                     let redraw_table = props.redraw_table.clone();
+                    let last_sort = props.last_sort.clone();
                     Callback::from(move |_: MouseEvent| {
                         let mut ref_vec = articles.deref().borrow_mut();
                         ref_vec.deref_mut().sort_by_key(|a| std::cmp::Reverse(a.$field.clone().unwrap_or_default()));
+                        *last_sort.borrow_mut() = Some(SortState { column: stringify!($field).to_string(), descending: true });
                         redraw_table.emit(());
                     })
                 };
                 let sort = {
                     let articles = props.articles.clone();
                     let redraw_table = props.redraw_table.clone();
+                    let last_sort = props.last_sort.clone();
                     Callback::from(move |_: MouseEvent| {
                         let mut ref_vec = articles.deref().borrow_mut();
                         ref_vec.deref_mut().sort_by_key(|a| a.$field.clone().unwrap_or_default());
+                        *last_sort.borrow_mut() = Some(SortState { column: stringify!($field).to_string(), descending: false });
                         redraw_table.emit(());
                     })
                 };
 
                 html! {
                     <th class="text-start hover-overlay" style={props.style.clone()}>
-                        <div class="row"><strong>{inflections::case::to_title_case(&stringify!{[<$field:snake>]})}</strong></div>
+                        <div class="row"><strong title={props.tooltip.clone()}>
+                            {inflections::case::to_title_case(&stringify!{[<$field:snake>]})}
+                            if props.tooltip.is_some() {
+                                <i class="bi bi-info-circle ms-1"></i>
+                            }
+                        </strong></div>
                         <button class="btn btn-outline-secondary col" onclick={sort_reverse}><i class="bi bi-sort-up"></i></button>
                         <button class="btn btn-outline-secondary col" onclick={sort}><i class="bi bi-sort-down"></i></button>
                         
@@ -210,17 +834,28 @@ macro_rules! header_cell {
             #[function_component]
             fn [<HeaderCellSearch $field:camel>](props: &HeaderCellSearchProps) -> Html {
                 let input_node_ref = use_node_ref();
+                let debounce = use_mut_ref(|| None::<Timeout>);
                 let oninput = {
                     let filters = props.filters.clone();
                     let input_node_ref = input_node_ref.clone();
-                    let redraw_table = props.redraw_table.clone();
+                    let refilter_table = props.refilter_table.clone();
                     Callback::from(move |_: InputEvent| {
                         let rc = filters.deref().to_owned();
                         let value = input_node_ref.cast::<web_sys::HtmlInputElement>().unwrap().value();
-                        rc.deref().borrow_mut().$field = value.as_str().into();
-                        redraw_table.emit(())
+                        rc.deref().borrow_mut().$field = normalize(&value);
+                        let refilter_table = refilter_table.clone();
+                        *debounce.borrow_mut() = Some(Timeout::new(FILTER_DEBOUNCE_MS, move || refilter_table.emit(())));
                     })
                 };
+                {
+                    let input_node_ref = input_node_ref.clone();
+                    let filters = props.filters.clone();
+                    use_effect_with(props.restore_version, move |_| {
+                        if let Some(input) = input_node_ref.cast::<web_sys::HtmlInputElement>() {
+                            input.set_value(&filters.deref().borrow().$field);
+                        }
+                    });
+                }
             
                 html! {
                     <th><div class="form-check ps-0"><input type="text" class="form-control" oninput={oninput} ref={input_node_ref}/></div></th>
@@ -238,30 +873,72 @@ header_cell!(citations);
 header_cell!(first_author);
 header_cell!(year_published);
 header_cell!(score);
+header_cell!(score_per_citation);
+header_cell!(score_per_seed);
+header_cell!(percentile_rank);
+header_cell!(sources);
+header_cell!(max_score);
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct TableGlobalSearchProps {
     filter: UseStateHandle<String>,
+    restore_version: u32,
 }
 
 #[function_component(TableGlobalSearch)]
 fn table_global_filter(props: &TableGlobalSearchProps) -> Html {
     let input_node_ref = use_node_ref();
+    let debounce = use_mut_ref(|| None::<Timeout>);
     let oninput = {
         let filter = props.filter.clone();
         let input_node_ref = input_node_ref.clone();
         Callback::from(move |_: InputEvent| {
             let value = input_node_ref.cast::<web_sys::HtmlInputElement>().unwrap().value();
-            filter.set(value);
+            let filter = filter.clone();
+            *debounce.borrow_mut() = Some(Timeout::new(FILTER_DEBOUNCE_MS, move || filter.set(value)));
         })
     };
+    {
+        let input_node_ref = input_node_ref.clone();
+        let filter = props.filter.clone();
+        use_effect_with(props.restore_version, move |_| {
+            if let Some(input) = input_node_ref.cast::<web_sys::HtmlInputElement>() {
+                input.set_value(&filter);
+            }
+        });
+    }
 
     html! {
-        <div class="row justify-content-end">
-            <div class="mb-3 form-check col" style="max-width: 20%">
-                <label class="form-label">{"Search all fields"}</label>
-                <input type="text" class="form-control" oninput={oninput} ref={input_node_ref}/>
-            </div>
+        <div class="mb-3 form-check col" style="max-width: 20%">
+            <label class="form-label">{"Search all fields"}</label>
+            <input type="text" class="form-control" oninput={oninput} ref={input_node_ref}/>
+        </div>
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct ExpandAllButtonProps {
+    expand_all: UseStateHandle<bool>,
+}
+
+#[function_component(ExpandAllButton)]
+fn expand_all_button(props: &ExpandAllButtonProps) -> Html {
+    let onclick = {
+        let expand_all = props.expand_all.clone();
+        Callback::from(move |_: MouseEvent| {
+            expand_all.set(!*expand_all);
+        })
+    };
+
+    html! {
+        <div class="mb-3 col-auto">
+            <button class="btn btn-outline-secondary" {onclick}>
+                if *props.expand_all {
+                    <i class="bi bi-arrows-collapse me-2"></i>{"Collapse all summaries"}
+                } else {
+                    <i class="bi bi-arrows-expand me-2"></i>{"Expand all summaries"}
+                }
+            </button>
         </div>
     }
 }
@@ -269,52 +946,181 @@ fn table_global_filter(props: &TableGlobalSearchProps) -> Html {
 #[derive(Clone, PartialEq, Properties)]
 pub struct RowProps {
     article: Article,
-    update_selected: Callback<(String, bool)>
+    focused: bool,
+    selected: bool,
+    update_selected: Callback<(usize, bool)>,
+    decision: Option<Decision>,
+    note: Option<String>,
+    on_decide: Callback<(usize, Option<Decision>)>,
+    on_note: Callback<(usize, String)>,
+    expanded: bool,
+    toggle_expanded: Callback<usize>,
+    show_details: Callback<Article>,
+    show_provenance: bool,
 }
 #[function_component(Row)]
 pub fn row(props: &RowProps) -> Html {
-    fn doi_link(doi: Option<String>) -> Option<String> {
-        let doi = doi?;
-        Some(format!("https://doi.org/{}", doi))
-    }
-
     let onchange = {
         let update_selected = props.update_selected.clone();
-        let doi = props.article.doi.clone();
+        let id = props.article.id;
         Callback::from(move |event: Event| {
-            let update_selected = update_selected.clone();
-            let doi = doi.clone();
             let checked = event.target_unchecked_into::<web_sys::HtmlInputElement>().checked();
-            if let Some(doi) = doi { update_selected.emit((doi, checked)) }
+            update_selected.emit((id, checked))
+        })
+    };
+
+    let toggle_expanded = {
+        let toggle_expanded = props.toggle_expanded.clone();
+        let id = props.article.id;
+        Callback::from(move |_: MouseEvent| {
+            toggle_expanded.emit(id);
+        })
+    };
+    let onclick_details = {
+        let show_details = props.show_details.clone();
+        let article = props.article.clone();
+        Callback::from(move |_: MouseEvent| {
+            show_details.emit(article.clone());
         })
     };
+    let summary_class = match props.expanded {
+        true => "summary",
+        false => "summary summary-collapsed"
+    };
 
     html! {
-        <tr>
-            <td><input type={"checkbox"} class={"row-checkbox"} onchange={onchange}/></td>
-            <td style=""><a href={doi_link(props.article.doi.clone())} style="word-wrap: break-word">{props.article.doi.clone().unwrap_or_default()}</a></td>
-            <td style="word-wrap: break-word">{props.article.title.clone().unwrap_or_default()}</td>
+        <tr id={format!("article-{}", props.article.id)} class={classes!(props.focused.then_some("table-active"), screening::row_class(props.decision))}>
+            <td>
+                <input type={"checkbox"} class={"row-checkbox"} checked={props.selected} onchange={onchange}/>
+                <button class="btn btn-link btn-sm p-0 d-block" title="Show details" onclick={onclick_details}><i class="bi bi-layout-sidebar-reverse"></i></button>
+                <DecisionButtons id={props.article.id} decision={props.decision} on_decide={props.on_decide.clone()}/>
+            </td>
+            <td style=""><a href={props.article.doi_link()} style="word-wrap: break-word">{props.article.doi.clone().unwrap_or_default()}</a></td>
+            <td style="word-wrap: break-word">
+                {props.article.title.clone().unwrap_or_default()}
+                if let Some(publication_type) = props.article.publication_type.clone() {
+                    <div><span class="badge text-bg-secondary">{publication_type}</span></div>
+                }
+                <NoteInput id={props.article.id} note={props.note.clone()} on_note={props.on_note.clone()}/>
+            </td>
             <td style="word-wrap: break-word">{props.article.journal.clone().unwrap_or_default()}</td>
-            <td>{props.article.first_author.clone().unwrap_or_default()}</td>
+            <td title={props.article.authors_joined()}>
+                {props.article.first_author.clone().unwrap_or_default()}
+                if props.article.authors.as_ref().is_some_and(|authors| authors.len() > 1) {
+                    <span class="text-body-secondary">{" et al."}</span>
+                }
+            </td>
             <td>{props.article.year_published.unwrap_or_default()}</td>
-            <td>{props.article.summary.clone().unwrap_or_default()}</td>
+            <td>
+                <div class={summary_class}>{props.article.summary.clone().unwrap_or_default()}</div>
+                if props.article.summary.is_some() {
+                    <button class="btn btn-link btn-sm p-0" onclick={toggle_expanded}>
+                        if props.expanded { {"Show less"} } else { {"Show more"} }
+                    </button>
+                }
+            </td>
             <td>{props.article.citations.unwrap_or_default()}</td>
             <td>{props.article.score.unwrap_or_default()}</td>
+            <td>{props.article.score_per_citation.map(|x| x.to_string()).unwrap_or_default()}</td>
+            <td>{props.article.score_per_seed.map(|x| x.to_string()).unwrap_or_default()}</td>
+            <td>{props.article.percentile_rank.map(|x| x.to_string()).unwrap_or_default()}</td>
+            if props.show_provenance {
+                <td>{props.article.sources_joined().unwrap_or_default()}</td>
+                <td>{props.article.max_score.map(|x| x.to_string()).unwrap_or_default()}</td>
+            }
         </tr>
     }
 }
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct ErrorProps {
-    msg: AttrValue
+    pub report: ErrorReport
+}
+
+fn focus_element(id: &str) {
+    let element = gloo_utils::document()
+        .get_element_by_id(id)
+        .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok());
+    if let Some(element) = element {
+        element.scroll_into_view();
+        element.focus().unwrap_or(());
+    }
 }
 
 #[function_component(Error)]
 pub fn error(props: &ErrorProps) -> Html {
+    let copied = use_state(|| false);
+    let oncopy = {
+        let details = props.report.details.clone();
+        let copied = copied.clone();
+        Callback::from(move |_: MouseEvent| {
+            let promise = match copy_to_clipboard(&details) {
+                Ok(promise) => promise,
+                Err(error) => {
+                    gloo_console::log!(format!("{error}"));
+                    return
+                }
+            };
+            let copied = copied.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                copied.set(wasm_bindgen_futures::JsFuture::from(promise).await.is_ok());
+            });
+        })
+    };
+
+    let action = match props.report.action {
+        RecoveryAction::FixIds => html! {
+            <button class="btn btn-outline-danger btn-sm" onclick={Callback::from(|_: MouseEvent| focus_element("idInput"))}>{"Check the identifiers"}</button>
+        },
+        RecoveryAction::ReduceDepth => html! {
+            <button class="btn btn-outline-danger btn-sm" onclick={Callback::from(|_: MouseEvent| focus_element("depthSelect"))}>{"Reduce the depth or the number of results"}</button>
+        },
+        RecoveryAction::Retry => {
+            let onclick = Callback::from(|_: MouseEvent| {
+                let form = gloo_utils::document()
+                    .get_element_by_id("snowballForm")
+                    .and_then(|element| element.dyn_into::<web_sys::HtmlFormElement>().ok());
+                if let Some(form) = form {
+                    form.request_submit().unwrap_or(());
+                }
+            });
+            html! { <button class="btn btn-outline-danger btn-sm" {onclick}>{"Try again"}</button> }
+        },
+        RecoveryAction::Reload => {
+            let onclick = Callback::from(|_: MouseEvent| {
+                gloo_utils::window().location().reload().unwrap_or(());
+            });
+            html! { <button class="btn btn-outline-danger btn-sm" {onclick}>{"Reload the page"}</button> }
+        },
+        RecoveryAction::ChooseFile => html! {
+            <button class="btn btn-outline-danger btn-sm" onclick={Callback::from(|_: MouseEvent| focus_element("importInput"))}>{"Choose another file"}</button>
+        },
+        RecoveryAction::SwitchBrowser => html! {
+            <span>{"Searching works in any browser, the extension requires opening BibliZap in Firefox or Chrome."}</span>
+        },
+        RecoveryAction::ReportBug => html! {
+            <span>{"If the problem persists, please send us the details below through the Contact page."}</span>
+        }
+    };
+
     html! {
         <div class="container-fluid">
             <div class="alert alert-danger" role="alert">
-                {props.msg.to_owned()}
+                <p class="mb-2">{props.report.explanation}</p>
+                {action}
+                if !props.report.details.is_empty() {
+                    <details class="mt-2">
+                        <summary>{"Details"}</summary>
+                        <pre class="mb-2" style="white-space: pre-wrap">{props.report.details.clone()}</pre>
+                        <button class="btn btn-outline-secondary btn-sm" onclick={oncopy}>
+                            if *copied {
+                                <i class="bi bi-clipboard-check me-2"></i>{"Copied"}
+                            } else {
+                                <i class="bi bi-clipboard me-2"></i>{"Copy for a bug report"}
+                            }
+                        </button>
+                    </details>
+                }
             </div>
         </div>
     }
@@ -0,0 +1,133 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::table::Article;
+
+const WIDTH: f64 = 1000.0;
+const HEIGHT: f64 = 120.0;
+
+pub fn sorted_scores(articles: &[Article]) -> Vec<i32> {
+    let mut scores = articles.iter().filter_map(|article| article.score).collect::<Vec<_>>();
+    scores.sort_unstable_by(|a, b| b.cmp(a));
+    scores
+}
+
+/// Index of the elbow of a decreasing curve, i.e. the point farthest from the chord joining its ends (Kneedle)
+pub fn elbow_index(scores: &[i32]) -> Option<usize> {
+    let (first, last) = (*scores.first()?, *scores.last()?);
+    if scores.len() < 3 || first == last {
+        return None;
+    }
+
+    let n = (scores.len() - 1) as f64;
+    let range = (first - last) as f64;
+    scores
+        .iter()
+        .enumerate()
+        // Both axes are scaled to [0, 1], the chord becoming y = 1 - x
+        .map(|(i, score)| (i, (*score - last) as f64 / range - (1.0 - i as f64 / n)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct ScoreDistributionProps {
+    pub articles: Rc<RefCell<Vec<Article>>>,
+    pub articles_version: u32,
+    pub min_score: Option<i32>,
+    pub on_cutoff: Callback<Option<i32>>
+}
+
+#[function_component(ScoreDistribution)]
+pub fn score_distribution(props: &ScoreDistributionProps) -> Html {
+    let scores = {
+        let articles = props.articles.clone();
+        use_memo((Rc::as_ptr(&props.articles) as usize, props.articles_version), move |_| sorted_scores(&articles.borrow()))
+    };
+    let suggest = use_state(|| false);
+
+    let ontoggle = {
+        let suggest = suggest.clone();
+        Callback::from(move |_: Event| {
+            suggest.set(!*suggest);
+        })
+    };
+
+    let (Some(max_score), Some(min_score)) = (scores.first().copied(), scores.last().copied()) else {
+        return html! {};
+    };
+
+    let x = |i: usize| WIDTH * i as f64 / (scores.len().max(2) - 1) as f64;
+    let y = |score: i32| HEIGHT - HEIGHT * (score - min_score) as f64 / (max_score - min_score).max(1) as f64;
+    let points = scores
+        .iter()
+        .enumerate()
+        .map(|(i, score)| format!("{:.1},{:.1}", x(i), y(*score)))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let elbow = elbow_index(&scores).filter(|_| *suggest);
+    let above = |cutoff: i32| scores.iter().take_while(|score| **score >= cutoff).count();
+
+    let cutoff_line = |score: i32, color: &'static str| {
+        let position = above(score).saturating_sub(1);
+        html! {
+            <line x1={x(position).to_string()} y1="0" x2={x(position).to_string()} y2={HEIGHT.to_string()}
+                stroke={color} stroke-dasharray="4 3"/>
+        }
+    };
+
+    let suggestion = elbow.map(|index| {
+        let cutoff = scores[index];
+        let applied = props.min_score == Some(cutoff);
+        let onclick = {
+            let on_cutoff = props.on_cutoff.clone();
+            Callback::from(move |_: MouseEvent| {
+                on_cutoff.emit((!applied).then_some(cutoff));
+            })
+        };
+        html! {
+            <div class="d-flex align-items-center gap-2">
+                <small>{format!("Relevance drops off below a score of {cutoff} : {} of {} articles lie above it.", above(cutoff), scores.len())}</small>
+                <button class="btn btn-outline-primary btn-sm" {onclick}>
+                    {if applied { "Remove cut-off" } else { "Apply cut-off" }}
+                </button>
+            </div>
+        }
+    });
+
+    let onclear = {
+        let on_cutoff = props.on_cutoff.clone();
+        Callback::from(move |_: MouseEvent| {
+            on_cutoff.emit(None);
+        })
+    };
+
+    html! {
+        <div class="mb-3">
+            <div class="d-flex align-items-center gap-3">
+                <small class="text-body-secondary">{format!("Score distribution, from {max_score} down to {min_score}")}</small>
+                <div class="form-check form-switch mb-0">
+                    <input class="form-check-input" type="checkbox" role="switch" id="suggestCutoffSwitch" checked={*suggest} onchange={ontoggle}/>
+                    <label class="form-check-label" for="suggestCutoffSwitch"><small>{"Suggest a cut-off"}</small></label>
+                </div>
+                if let Some(min_score) = props.min_score {
+                    <span class="badge text-bg-primary">{format!("Score ≥ {min_score}")}</span>
+                    <button class="btn btn-outline-secondary btn-sm" onclick={onclear}>{"Clear"}</button>
+                }
+            </div>
+            <svg class="score-distribution" viewBox={format!("0 0 {WIDTH} {HEIGHT}")} preserveAspectRatio="none">
+                <polyline points={points} fill="none" stroke="var(--bs-primary)" stroke-width="2" vector-effect="non-scaling-stroke"/>
+                if let Some(index) = elbow {
+                    {cutoff_line(scores[index], "var(--bs-danger)")}
+                }
+                if let Some(min_score) = props.min_score {
+                    {cutoff_line(min_score, "var(--bs-secondary-color)")}
+                }
+            </svg>
+            {suggestion}
+        </div>
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Decision {
+    Include,
+    Exclude,
+}
+
+#[derive(Default, PartialEq, Debug)]
+pub struct Screening {
+    pub decisions: BTreeMap<usize, Decision>,
+    pub notes: BTreeMap<usize, String>,
+}
+
+pub enum ScreeningAction {
+    Decide(usize, Option<Decision>),
+    Note(usize, String),
+    Restore(BTreeMap<usize, Decision>, BTreeMap<usize, String>),
+    Remove(Rc<Vec<usize>>),
+}
+
+impl Reducible for Screening {
+    type Action = ScreeningAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut decisions = self.decisions.clone();
+        let mut notes = self.notes.clone();
+        match action {
+            ScreeningAction::Decide(id, Some(decision)) => { decisions.insert(id, decision); },
+            ScreeningAction::Decide(id, None) => { decisions.remove(&id); },
+            ScreeningAction::Note(id, note) => match note.trim().is_empty() {
+                true => { notes.remove(&id); },
+                false => { notes.insert(id, note); }
+            },
+            ScreeningAction::Restore(restored_decisions, restored_notes) => {
+                decisions = restored_decisions;
+                notes = restored_notes;
+            },
+            ScreeningAction::Remove(removed) => {
+                decisions.retain(|id, _| !removed.contains(id));
+                notes.retain(|id, _| !removed.contains(id));
+            },
+        }
+        Rc::new(Screening { decisions, notes })
+    }
+}
+
+pub fn row_class(decision: Option<Decision>) -> Option<&'static str> {
+    match decision? {
+        Decision::Include => Some("table-success"),
+        Decision::Exclude => Some("table-danger"),
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct DecisionButtonsProps {
+    pub id: usize,
+    pub decision: Option<Decision>,
+    pub on_decide: Callback<(usize, Option<Decision>)>,
+}
+
+#[function_component(DecisionButtons)]
+pub fn decision_buttons(props: &DecisionButtonsProps) -> Html {
+    // Clicking the current decision withdraws it
+    let button = |decision: Decision, class: &'static str, icon: &'static str, label: &'static str| {
+        let chosen = props.decision == Some(decision);
+        let onclick = {
+            let on_decide = props.on_decide.clone();
+            let id = props.id;
+            Callback::from(move |_: MouseEvent| {
+                on_decide.emit((id, (!chosen).then_some(decision)));
+            })
+        };
+        html! {
+            <button type="button" class={classes!("btn", "btn-sm", if chosen { class } else { "btn-outline-secondary" })}
+                title={label} aria-label={label} aria-pressed={chosen.to_string()} {onclick}>
+                <i class={icon}></i>
+            </button>
+        }
+    };
+
+    html! {
+        <div class="btn-group btn-group-sm" role="group" aria-label="Screening decision">
+            { button(Decision::Include, "btn-success", "bi bi-check-lg", "Include") }
+            { button(Decision::Exclude, "btn-danger", "bi bi-x-lg", "Exclude") }
+        </div>
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct NoteInputProps {
+    pub id: usize,
+    pub note: Option<String>,
+    pub on_note: Callback<(usize, String)>,
+    #[prop_or_default]
+    pub multiline: bool,
+}
+
+#[function_component(NoteInput)]
+pub fn note_input(props: &NoteInputProps) -> Html {
+    let onchange = {
+        let on_note = props.on_note.clone();
+        let id = props.id;
+        Callback::from(move |event: Event| {
+            let value = event.target_unchecked_into::<web_sys::HtmlInputElement>().value();
+            on_note.emit((id, value));
+        })
+    };
+    let value = props.note.clone().unwrap_or_default();
+
+    match props.multiline {
+        true => html! {
+            <textarea class="form-control" rows="3" placeholder="Note" aria-label="Note" {value} {onchange}></textarea>
+        },
+        false => html! {
+            <input type="text" class="form-control form-control-sm mt-1" placeholder="Note" aria-label="Note" {value} {onchange}/>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reduce(screening: Screening, action: ScreeningAction) -> Screening {
+        Rc::into_inner(Rc::new(screening).reduce(action)).unwrap()
+    }
+
+    #[test]
+    fn decisions_are_set_and_withdrawn() {
+        let screening = reduce(Screening::default(), ScreeningAction::Decide(3, Some(Decision::Include)));
+        let screening = reduce(screening, ScreeningAction::Decide(3, Some(Decision::Exclude)));
+        assert_eq!(screening.decisions, BTreeMap::from([(3, Decision::Exclude)]));
+
+        let screening = reduce(screening, ScreeningAction::Decide(3, None));
+        assert!(screening.decisions.is_empty());
+    }
+
+    #[test]
+    fn blank_notes_are_dropped() {
+        let screening = reduce(Screening::default(), ScreeningAction::Note(1, "Check the protocol".to_string()));
+        assert_eq!(screening.notes, BTreeMap::from([(1, "Check the protocol".to_string())]));
+
+        let screening = reduce(screening, ScreeningAction::Note(1, "  ".to_string()));
+        assert!(screening.notes.is_empty());
+    }
+
+    #[test]
+    fn merged_articles_lose_their_screening() {
+        let screening = reduce(Screening::default(), ScreeningAction::Restore(
+            BTreeMap::from([(1, Decision::Include), (2, Decision::Exclude)]),
+            BTreeMap::from([(2, "Duplicate".to_string())])
+        ));
+        let screening = reduce(screening, ScreeningAction::Remove(Rc::new(vec![2])));
+        assert_eq!(screening.decisions, BTreeMap::from([(1, Decision::Include)]));
+        assert!(screening.notes.is_empty());
+    }
+}
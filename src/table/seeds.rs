@@ -0,0 +1,48 @@
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::api::SeedResolution;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct SeedReportProps {
+    pub seeds: Rc<Vec<SeedResolution>>
+}
+
+#[function_component(SeedReport)]
+pub fn seed_report(props: &SeedReportProps) -> Html {
+    if props.seeds.is_empty() {
+        return html! {};
+    }
+
+    let failed_count = props.seeds.iter().filter(|seed| !seed.resolved).count();
+    let (class, summary) = match failed_count {
+        0 => ("alert alert-success", format!("All {} seeds were resolved", props.seeds.len())),
+        _ => ("alert alert-warning", format!("{} of {} seeds could not be resolved and were ignored", failed_count, props.seeds.len()))
+    };
+
+    html! {
+        <div class="container-md">
+            <div class={class} role="status">
+                <details open={failed_count > 0}>
+                    <summary>{summary}</summary>
+                    <ul class="list-unstyled mt-2 mb-0">
+                        { props.seeds.iter().map(|seed| html! {
+                            <li>
+                                if seed.resolved {
+                                    <i class="bi bi-check-circle-fill text-success me-2"></i>
+                                    <code>{seed.input.clone()}</code>
+                                    {seed.title.as_ref().map(|title| format!(" — {title}")).unwrap_or_default()}
+                                } else {
+                                    <i class="bi bi-x-circle-fill text-danger me-2"></i>
+                                    <code>{seed.input.clone()}</code>
+                                    {seed.reason.as_ref().map(|reason| format!(" — {reason}")).unwrap_or_default()}
+                                }
+                            </li>
+                        }).collect::<Html>() }
+                    </ul>
+                </details>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,75 @@
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+/// Set of selected article ids, kept outside of the rows so that it survives sorting and pagination
+#[derive(Default, PartialEq, Debug)]
+pub struct Selection {
+    pub ids: HashSet<usize>,
+}
+
+pub enum SelectionAction {
+    Set(usize, bool),
+    SelectAll(Rc<Vec<usize>>),
+    Invert(Rc<Vec<usize>>),
+    Remove(Rc<Vec<usize>>),
+    Clear,
+}
+
+impl Reducible for Selection {
+    type Action = SelectionAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut ids = self.ids.clone();
+        match action {
+            SelectionAction::Set(id, true) => { ids.insert(id); },
+            SelectionAction::Set(id, false) => { ids.remove(&id); },
+            SelectionAction::SelectAll(new_ids) => ids.extend(new_ids.iter()),
+            SelectionAction::Invert(scope) => {
+                for id in scope.iter() {
+                    if !ids.remove(id) {
+                        ids.insert(*id);
+                    }
+                }
+            },
+            SelectionAction::Remove(removed) => ids.retain(|id| !removed.contains(id)),
+            SelectionAction::Clear => ids.clear(),
+        }
+        Rc::new(Selection { ids })
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct SelectionToolbarProps {
+    pub selection: UseReducerHandle<Selection>,
+    pub page_ids: Rc<Vec<usize>>,
+    pub filtered_ids: Rc<Vec<usize>>,
+}
+
+#[function_component(SelectionToolbar)]
+pub fn selection_toolbar(props: &SelectionToolbarProps) -> Html {
+    let dispatch = |make_action: fn(&SelectionToolbarProps) -> SelectionAction| {
+        let props = props.clone();
+        Callback::from(move |_: MouseEvent| {
+            props.selection.dispatch(make_action(&props));
+        })
+    };
+
+    let select_page = dispatch(|props| SelectionAction::SelectAll(props.page_ids.clone()));
+    let select_filtered = dispatch(|props| SelectionAction::SelectAll(props.filtered_ids.clone()));
+    let invert = dispatch(|props| SelectionAction::Invert(props.filtered_ids.clone()));
+    let clear = dispatch(|_| SelectionAction::Clear);
+
+    html! {
+        <div class="mb-3 col d-flex align-items-center gap-2">
+            <span class="me-2">{format!("{} selected", props.selection.ids.len())}</span>
+            <div class="btn-group" role="group" aria-label="Selection">
+                <button class="btn btn-outline-secondary" onclick={select_page}>{"Select page"}</button>
+                <button class="btn btn-outline-secondary" onclick={select_filtered}>{format!("Select all {} filtered", props.filtered_ids.len())}</button>
+                <button class="btn btn-outline-secondary" onclick={invert}>{"Invert"}</button>
+                <button class="btn btn-outline-secondary" onclick={clear}>{"Clear"}</button>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,119 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::table::Article;
+use crate::table::filter::normalize;
+
+const TOP_ENTRIES: usize = 10;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Facet {
+    Journal,
+    FirstAuthor
+}
+
+pub fn top_values<'a>(values: impl Iterator<Item = &'a str>, limit: usize) -> Vec<(String, usize)> {
+    let mut counts = HashMap::<String, (String, usize)>::new();
+    for value in values {
+        counts.entry(normalize(value)).or_insert_with(|| (value.to_string(), 0)).1 += 1;
+    }
+    let mut counts = counts.into_values().collect::<Vec<_>>();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(limit);
+    counts
+}
+
+#[derive(Clone, PartialEq, Properties)]
+struct FacetListProps {
+    title: AttrValue,
+    entries: Vec<(String, usize)>,
+    active: Option<String>,
+    on_select: Callback<Option<String>>
+}
+
+#[function_component(FacetList)]
+fn facet_list(props: &FacetListProps) -> Html {
+    let entries = props.entries.iter().map(|(value, count)| {
+        let active = props.active.as_deref() == Some(normalize(value).as_str());
+        let onclick = {
+            let on_select = props.on_select.clone();
+            let value = value.clone();
+            Callback::from(move |_: MouseEvent| {
+                on_select.emit((!active).then(|| value.clone()));
+            })
+        };
+        html! {
+            <button type="button" class={classes!("list-group-item", "list-group-item-action", "d-flex", "justify-content-between", "align-items-center", active.then_some("active"))}
+                aria-pressed={active.to_string()} {onclick}>
+                <span class="text-truncate">{value}</span>
+                <span class="badge text-bg-secondary rounded-pill ms-2">{count}</span>
+            </button>
+        }
+    }).collect::<Html>();
+
+    html! {
+        <div class="col-md-6">
+            <h6>{props.title.clone()}</h6>
+            if props.entries.is_empty() {
+                <p class="text-body-secondary">{"No data"}</p>
+            } else {
+                <div class="list-group list-group-flush">{entries}</div>
+            }
+        </div>
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct StatisticsPanelProps {
+    pub articles: Rc<RefCell<Vec<Article>>>,
+    pub indices: Rc<Vec<usize>>,
+    pub journal: Option<String>,
+    pub first_author: Option<String>,
+    pub on_select: Callback<(Facet, Option<String>)>
+}
+
+#[function_component(StatisticsPanel)]
+pub fn statistics_panel(props: &StatisticsPanelProps) -> Html {
+    let open = use_state(|| false);
+    let ontoggle = {
+        let open = open.clone();
+        Callback::from(move |_: MouseEvent| {
+            open.set(!*open);
+        })
+    };
+
+    let facet_callback = |facet: Facet| {
+        let on_select = props.on_select.clone();
+        Callback::from(move |value: Option<String>| on_select.emit((facet, value)))
+    };
+
+    let (journals, first_authors) = match *open {
+        true => {
+            let articles = props.articles.borrow();
+            let filtered = || props.indices.iter().map(|index| &articles[*index]);
+            (
+                top_values(filtered().filter_map(|article| article.journal.as_deref()), TOP_ENTRIES),
+                top_values(filtered().filter_map(|article| article.first_author.as_deref()), TOP_ENTRIES)
+            )
+        },
+        false => Default::default()
+    };
+
+    html! {
+        <div class="mb-3">
+            <button class="btn btn-outline-secondary btn-sm" aria-expanded={open.to_string()} onclick={ontoggle}>
+                <i class={classes!("bi", "me-2", if *open { "bi-chevron-down" } else { "bi-chevron-right" })}></i>
+                {"Top journals and authors"}
+            </button>
+            if *open {
+                <div class="row mt-2">
+                    <FacetList title="Journals" entries={journals} active={props.journal.clone()} on_select={facet_callback(Facet::Journal)}/>
+                    <FacetList title="First authors" entries={first_authors} active={props.first_author.clone()} on_select={facet_callback(Facet::FirstAuthor)}/>
+                </div>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,16 @@
+use std::ops::Range;
+
+/// Estimated height of a table row in pixels, summaries being collapsed to a few lines
+pub const ROW_HEIGHT: f64 = 150.0;
+pub const OVERSCAN: usize = 10;
+pub const THRESHOLD: usize = 100;
+
+pub fn visible_rows(scroll_top: f64, viewport_height: f64, row_count: usize) -> Range<usize> {
+    let first_visible = (scroll_top.max(0.0) / ROW_HEIGHT).floor() as usize;
+    let visible_count = (viewport_height.max(0.0) / ROW_HEIGHT).ceil() as usize + 1;
+
+    let start = first_visible.saturating_sub(OVERSCAN).min(row_count);
+    let end = (first_visible + visible_count + OVERSCAN).min(row_count);
+
+    start..end
+}